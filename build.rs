@@ -3,21 +3,75 @@ mod frensfile;
 
 use anyhow::{Ok, Result};
 use indoc::formatdoc;
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::Write;
 use std::path::Path;
 use substreams_ethereum::Abigen;
 
-use frensfile::{parse_frens_file, FrensFile};
+use frensfile::{
+    parse_frens_file, FrensFile, PriceFeed, PricePool, RebasingToken, StreamContract, TokenMetadata,
+};
 
 fn main() -> Result<(), anyhow::Error> {
     // Generate ABIs:
     abigen()?;
 
     // Generate Addresses
-    let frens_file = parse_frens_file("./frens.yaml".to_string()).unwrap();
-    write_settings_file(&frens_file);
-    write_schema_settings_file(&frens_file);
+    let mut frens_file = parse_frens_file("./frens.yaml".to_string()).unwrap();
+    let schema_name = frens_file.schema_name();
+    let table_prefix = frens_file.table_prefix();
+    if !is_sql_identifier(&schema_name)
+        || !(table_prefix.is_empty() || is_sql_identifier(&table_prefix))
+    {
+        panic!(
+            "Invalid database settings: schema_name and table_prefix may only contain lowercase letters, digits and _"
+        );
+    }
+
+    let tokens = frens_file.token_metadata();
+    let price_pools = frens_file.price_pools.take().unwrap_or_default();
+    let usd_tokens = frens_file.usd_tokens.take().unwrap_or_else(|| {
+        vec![
+            "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48".to_string(), // USDC
+            "0xdac17f958d2ee523a2206206994597c13d831ec7".to_string(), // USDT
+            "0x6b175474e89094c44da98b954eedeac495271d0f".to_string(), // DAI
+        ]
+    });
+    let mut price_feeds = frens_file.price_feeds.take().unwrap_or_default();
+    for feed in price_feeds.iter_mut() {
+        if feed.token_decimals.is_none() {
+            feed.token_decimals = if feed.token == "ETH" {
+                Some(18)
+            } else {
+                tokens
+                    .iter()
+                    .filter(|t| normalize_address(&t.address) == normalize_address(&feed.token))
+                    .filter_map(|t| t.decimals)
+                    .last()
+            };
+        }
+        if feed.token_decimals.is_none() {
+            panic!(
+                "Unknown decimals of token {} for price feed {}, set token_decimals",
+                feed.token, feed.name
+            );
+        }
+    }
+    let rebasing_tokens = frens_file.rebasing_tokens.take().unwrap_or_default();
+    let streams = frens_file.streams.take().unwrap_or_default();
+    let organizations = frens_file.all_organizations();
+    write_settings_file(
+        &organizations,
+        &price_pools,
+        &usd_tokens,
+        &price_feeds,
+        &rebasing_tokens,
+        &streams,
+        &table_prefix,
+    );
+    write_schema_file(&schema_name, &table_prefix);
+    write_schema_settings_file(&organizations, &tokens, &schema_name, &table_prefix);
 
     Ok(())
 }
@@ -39,29 +93,139 @@ fn abigen() -> Result<(), anyhow::Error> {
         .generate()?
         .write_to_file("src/abi/weth.rs")?;
 
+    Abigen::new("SafeProxyFactory", "abi/safe_proxy_factory.json")?
+        .generate()?
+        .write_to_file("src/abi/safe_proxy_factory.rs")?;
+
+    Abigen::new("UniswapV2Pair", "abi/uniswap_v2_pair.json")?
+        .generate()?
+        .write_to_file("src/abi/uniswap_v2_pair.rs")?;
+
+    Abigen::new("UniswapV3Pool", "abi/uniswap_v3_pool.json")?
+        .generate()?
+        .write_to_file("src/abi/uniswap_v3_pool.rs")?;
+
+    Abigen::new("ChainlinkAggregator", "abi/chainlink_aggregator.json")?
+        .generate()?
+        .write_to_file("src/abi/chainlink_aggregator.rs")?;
+
+    Abigen::new("ERC20Votes", "abi/erc20_votes.json")?
+        .generate()?
+        .write_to_file("src/abi/erc20_votes.rs")?;
+
+    Abigen::new("Governor", "abi/governor.json")?
+        .generate()?
+        .write_to_file("src/abi/governor.rs")?;
+
+    Abigen::new("TimelockController", "abi/timelock_controller.json")?
+        .generate()?
+        .write_to_file("src/abi/timelock_controller.rs")?;
+
+    Abigen::new("CompoundTimelock", "abi/compound_timelock.json")?
+        .generate()?
+        .write_to_file("src/abi/compound_timelock.rs")?;
+
+    Abigen::new("Lido", "abi/lido.json")?
+        .generate()?
+        .write_to_file("src/abi/lido.rs")?;
+
+    Abigen::new("AaveV2AToken", "abi/aave_v2_atoken.json")?
+        .generate()?
+        .write_to_file("src/abi/aave_v2_atoken.rs")?;
+
+    Abigen::new("AaveV3AToken", "abi/aave_v3_atoken.json")?
+        .generate()?
+        .write_to_file("src/abi/aave_v3_atoken.rs")?;
+
+    Abigen::new("ERC4626", "abi/erc4626.json")?
+        .generate()?
+        .write_to_file("src/abi/erc4626.rs")?;
+
+    Abigen::new("CurvePool", "abi/curve_pool.json")?
+        .generate()?
+        .write_to_file("src/abi/curve_pool.rs")?;
+
+    Abigen::new("BalancerVault", "abi/balancer_vault.json")?
+        .generate()?
+        .write_to_file("src/abi/balancer_vault.rs")?;
+
+    Abigen::new("AaveV2Pool", "abi/aave_v2_pool.json")?
+        .generate()?
+        .write_to_file("src/abi/aave_v2_pool.rs")?;
+
+    Abigen::new("AaveV3Pool", "abi/aave_v3_pool.json")?
+        .generate()?
+        .write_to_file("src/abi/aave_v3_pool.rs")?;
+
+    Abigen::new("CompoundCToken", "abi/compound_ctoken.json")?
+        .generate()?
+        .write_to_file("src/abi/compound_ctoken.rs")?;
+
+    Abigen::new(
+        "UniswapV3PositionManager",
+        "abi/uniswap_v3_position_manager.json",
+    )?
+    .generate()?
+    .write_to_file("src/abi/uniswap_v3_position_manager.rs")?;
+
+    Abigen::new("SablierV1", "abi/sablier_v1.json")?
+        .generate()?
+        .write_to_file("src/abi/sablier_v1.rs")?;
+
+    Abigen::new("SablierV2LockupLinear", "abi/sablier_v2_lockup_linear.json")?
+        .generate()?
+        .write_to_file("src/abi/sablier_v2_lockup_linear.rs")?;
+
+    Abigen::new("LlamaPay", "abi/llamapay.json")?
+        .generate()?
+        .write_to_file("src/abi/llamapay.rs")?;
+
+    Abigen::new("MerkleDistributor", "abi/merkle_distributor.json")?
+        .generate()?
+        .write_to_file("src/abi/merkle_distributor.rs")?;
+
     Ok(())
 }
 
 // Create src/settings/mod.rs with constants
-fn write_settings_file(frens_file: &FrensFile) {
+fn write_settings_file(
+    organizations: &Vec<FrensFile>,
+    price_pools: &Vec<PricePool>,
+    usd_tokens: &Vec<String>,
+    price_feeds: &Vec<PriceFeed>,
+    rebasing_tokens: &Vec<RebasingToken>,
+    streams: &Vec<StreamContract>,
+    table_prefix: &String,
+) {
     let out_dir = "./src/settings";
     let dest_path = Path::new(&out_dir).join("mod.rs");
     let mut f = File::create(&dest_path).unwrap();
 
-    let my_addresses: Vec<String> = frens_file
-        .all_addresses()
+    let mut my_addresses: Vec<String> = organizations
         .iter()
+        .flat_map(|o| o.all_addresses())
         .map(|t| normalize_address(&t))
         .collect();
+    my_addresses.sort();
+    my_addresses.dedup();
 
     let treasury_hex_lines: Vec<String> = my_addresses
         .iter()
         .map(|a| format!("hex!(\"{}\"),", a))
         .collect();
-    let issued_lines: Vec<String> = frens_file
-        .tokens_issued
+    let treasury_dao_lines: Vec<String> = organizations
         .iter()
-        .map(|t| {
+        .flat_map(|o| {
+            o.all_addresses()
+                .iter()
+                .map(|a| format!("(\"{}\", hex!(\"{}\")),", o.dao_id(), normalize_address(&a)))
+                .collect::<Vec<String>>()
+        })
+        .collect();
+    let issued_lines: Vec<String> = organizations
+        .iter()
+        .flat_map(|o| o.tokens_issued.iter().map(move |t| (o.dao_id(), t)))
+        .map(|(dao_id, t)| {
             let token_id: String = match &t.token_id {
                 Some(t) => t.to_string(),
                 None => "".to_string(),
@@ -77,6 +241,7 @@ fn write_settings_file(frens_file: &FrensFile) {
             format!(
                 "TokenIssued {{
             token_address: hex!(\"{}\"),
+            dao_id: \"{}\".to_string(),
             token_id: Some(\"{}\".to_string()),
             schema: Some(\"{}\".to_string()),
             name: \"{}\".to_string(),
@@ -85,6 +250,7 @@ fn write_settings_file(frens_file: &FrensFile) {
             initial_block: {},
         }},",
                 normalize_address(&t.address),
+                dao_id,
                 token_id,
                 schema,
                 t.name,
@@ -95,81 +261,608 @@ fn write_settings_file(frens_file: &FrensFile) {
         })
         .collect();
 
+    let factory_lines: Vec<String> = organizations
+        .iter()
+        .flat_map(|o| o.factories.iter().flatten().map(move |f| (o.dao_id(), f)))
+        .map(|(dao_id, f)| {
+            let network: String = match &f.network {
+                Some(n) => n.to_string(),
+                None => "mainnet".to_string(),
+            };
+            format!(
+                "Factory {{
+            factory_address: hex!(\"{}\"),
+            dao_id: \"{}\".to_string(),
+            name: {:?}.to_string(),
+            address: \"{}\".to_string(),
+            network: Some(\"{}\".to_string()),
+            initial_block: {},
+            treasury_only: Some({}),
+        }},",
+                normalize_address(&f.address),
+                dao_id,
+                f.name,
+                &f.address,
+                network,
+                f.initial_block,
+                f.treasury_only.unwrap_or(true)
+            )
+        })
+        .collect();
+
+    let governor_lines: Vec<String> = organizations
+        .iter()
+        .flat_map(|o| o.governance.iter().flatten().map(move |g| (o.dao_id(), g)))
+        .map(|(dao_id, g)| {
+            let network: String = match &g.network {
+                Some(n) => n.to_string(),
+                None => "mainnet".to_string(),
+            };
+            format!(
+                "Governor {{
+            governor_address: hex!(\"{}\"),
+            dao_id: \"{}\".to_string(),
+            name: {:?}.to_string(),
+            address: \"{}\".to_string(),
+            network: Some(\"{}\".to_string()),
+            initial_block: {},
+        }},",
+                normalize_address(&g.address),
+                dao_id,
+                g.name,
+                &g.address,
+                network,
+                g.initial_block
+            )
+        })
+        .collect();
+
+    let timelock_lines: Vec<String> = organizations
+        .iter()
+        .flat_map(|o| o.timelocks.iter().flatten().map(move |t| (o.dao_id(), t)))
+        .map(|(dao_id, t)| {
+            let network: String = match &t.network {
+                Some(n) => n.to_string(),
+                None => "mainnet".to_string(),
+            };
+            format!(
+                "Timelock {{
+            timelock_address: hex!(\"{}\"),
+            dao_id: \"{}\".to_string(),
+            name: {:?}.to_string(),
+            address: \"{}\".to_string(),
+            network: Some(\"{}\".to_string()),
+            initial_block: {},
+        }},",
+                normalize_address(&t.address),
+                dao_id,
+                t.name,
+                &t.address,
+                network,
+                t.initial_block
+            )
+        })
+        .collect();
+
+    let distributor_lines: Vec<String> = organizations
+        .iter()
+        .flat_map(|o| {
+            o.distributors
+                .iter()
+                .flatten()
+                .map(move |d| (o.dao_id(), d))
+        })
+        .map(|(dao_id, d)| {
+            let network: String = match &d.network {
+                Some(n) => n.to_string(),
+                None => "mainnet".to_string(),
+            };
+            let total: String = match &d.total {
+                Some(t) => format!("Some(\"{}\".to_string())", t),
+                None => "None".to_string(),
+            };
+            format!(
+                "Distributor {{
+            distributor_address: hex!(\"{}\"),
+            dao_id: \"{}\".to_string(),
+            name: {:?}.to_string(),
+            address: \"{}\".to_string(),
+            token: \"{}\".to_string(),
+            total: {},
+            network: Some(\"{}\".to_string()),
+            initial_block: {},
+        }},",
+                normalize_address(&d.address),
+                dao_id,
+                d.name,
+                &d.address,
+                normalize_address(&d.token),
+                total,
+                network,
+                d.initial_block
+            )
+        })
+        .collect();
+
+    let price_pool_lines: Vec<String> = price_pools
+        .iter()
+        .map(|p| {
+            let protocol: String = match &p.protocol {
+                Some(protocol) => protocol.to_string(),
+                None => "uniswap_v2".to_string(),
+            };
+            if protocol != "uniswap_v2" && protocol != "uniswap_v3" {
+                panic!(
+                    "Unsupported protocol {} for price pool {}",
+                    protocol, p.name
+                );
+            }
+            format!(
+                "PricePool {{
+            pool_address: hex!(\"{}\"),
+            name: {:?}.to_string(),
+            address: \"{}\".to_string(),
+            protocol: Some(\"{}\".to_string()),
+            token0: \"{}\".to_string(),
+            token1: \"{}\".to_string(),
+            decimals0: {},
+            decimals1: {},
+        }},",
+                normalize_address(&p.address),
+                p.name,
+                &p.address,
+                protocol,
+                normalize_address(&p.token0),
+                normalize_address(&p.token1),
+                p.decimals0,
+                p.decimals1
+            )
+        })
+        .collect();
+    let mut feed_tokens: Vec<String> = Vec::new();
+    let price_feed_lines: Vec<String> = price_feeds
+        .iter()
+        .map(|p| {
+            let token: String = if p.token == "ETH" {
+                p.token.to_string()
+            } else {
+                normalize_address(&p.token)
+            };
+            if feed_tokens.contains(&token) {
+                panic!("Token {} has more than one price feed", p.token);
+            }
+            feed_tokens.push(token.to_string());
+            format!(
+                "PriceFeed {{
+            feed_address: hex!(\"{}\"),
+            name: {:?}.to_string(),
+            address: \"{}\".to_string(),
+            token: \"{}\".to_string(),
+            decimals: Some({}),
+            token_decimals: Some({}),
+        }},",
+                normalize_address(&p.address),
+                p.name,
+                &p.address,
+                token,
+                p.decimals.unwrap_or(8),
+                p.token_decimals.unwrap()
+            )
+        })
+        .collect();
+    let rebasing_token_lines: Vec<String> = rebasing_tokens
+        .iter()
+        .map(|t| {
+            if !["lido", "aave_v2", "aave_v3"].contains(&t.protocol.as_str()) {
+                panic!(
+                    "Unsupported protocol {} of rebasing token {}",
+                    t.protocol, t.name
+                );
+            }
+            format!(
+                "RebasingToken {{
+            token_address: hex!(\"{}\"),
+            name: {:?}.to_string(),
+            address: \"{}\".to_string(),
+            protocol: \"{}\".to_string(),
+        }},",
+                normalize_address(&t.address),
+                t.name,
+                &t.address,
+                t.protocol
+            )
+        })
+        .collect();
+    let stream_lines: Vec<String> = streams
+        .iter()
+        .map(|s| {
+            let protocol: String = match &s.protocol {
+                Some(protocol) => protocol.to_string(),
+                None => "llamapay".to_string(),
+            };
+            if !["llamapay", "sablier_v1", "sablier_v2"].contains(&protocol.as_str()) {
+                panic!(
+                    "Unsupported protocol {} of stream contract {}",
+                    protocol, s.name
+                );
+            }
+            format!(
+                "StreamContract {{
+            contract_address: hex!(\"{}\"),
+            name: {:?}.to_string(),
+            address: \"{}\".to_string(),
+            protocol: Some(\"{}\".to_string()),
+        }},",
+                normalize_address(&s.address),
+                s.name,
+                &s.address,
+                protocol
+            )
+        })
+        .collect();
+    let usd_token_lines: Vec<String> = usd_tokens
+        .iter()
+        .map(|t| format!("\"{}\",", normalize_address(t)))
+        .collect();
+
     let output = formatdoc! {"
         // @generated
-        use crate::frensfile::{{TokenIssued}};
+        use crate::frensfile::{{
+            Distributor, Factory, Governor, PriceFeed, PricePool, RebasingToken, StreamContract,
+            Timelock, TokenIssued,
+        }};
         use substreams::hex;
 
+        pub const TABLE_PREFIX: &str = \"{}\";
+
         lazy_static! {{
             pub static ref TREASURY_ADDRESSES: [[u8;20] ; {}] = [
             {}
             ];
 
+            pub static ref TREASURY_ACCOUNT_DAOS: [(&'static str, [u8;20]) ; {}] = [
+            {}
+            ];
+
             pub static ref TOKENS_ISSUED: [TokenIssued ; {}] = [
             {}
             ];
+
+            pub static ref FACTORIES: [Factory ; {}] = [
+            {}
+            ];
+
+            pub static ref PRICE_POOLS: [PricePool ; {}] = [
+            {}
+            ];
+
+            pub static ref USD_TOKENS: [&'static str ; {}] = [
+            {}
+            ];
+
+            pub static ref PRICE_FEEDS: [PriceFeed ; {}] = [
+            {}
+            ];
+
+            pub static ref GOVERNORS: [Governor ; {}] = [
+            {}
+            ];
+
+            pub static ref TIMELOCKS: [Timelock ; {}] = [
+            {}
+            ];
+
+            pub static ref REBASING_TOKENS: [RebasingToken ; {}] = [
+            {}
+            ];
+
+            pub static ref DISTRIBUTORS: [Distributor ; {}] = [
+            {}
+            ];
+
+            pub static ref STREAM_CONTRACTS: [StreamContract ; {}] = [
+            {}
+            ];
         }}
     ",
+    table_prefix,
     treasury_hex_lines.len(), treasury_hex_lines.join("\n"),
+    treasury_dao_lines.len(), treasury_dao_lines.join("\n"),
     issued_lines.len(), issued_lines.join("\n"),
+    factory_lines.len(), factory_lines.join("\n"),
+    price_pool_lines.len(), price_pool_lines.join("\n"),
+    usd_token_lines.len(), usd_token_lines.join("\n"),
+    price_feed_lines.len(), price_feed_lines.join("\n"),
+    governor_lines.len(), governor_lines.join("\n"),
+    timelock_lines.len(), timelock_lines.join("\n"),
+    rebasing_token_lines.len(), rebasing_token_lines.join("\n"),
+    distributor_lines.len(), distributor_lines.join("\n"),
+    stream_lines.len(), stream_lines.join("\n"),
     };
 
     f.write_all(output.as_bytes()).unwrap();
 }
 
+// Render schema.tmpl.sql into the schema.sql file to be loaded into the DB
+fn write_schema_file(schema_name: &String, table_prefix: &String) {
+    let template =
+        std::fs::read_to_string("./schema.tmpl.sql").expect("Could not read schema.tmpl.sql");
+
+    // A prefixed deployment may share the schema with others, so only its own tables are dropped
+    let drop_schema: String = if table_prefix.is_empty() {
+        format!("drop schema if exists {} cascade;", schema_name)
+    } else {
+        template
+            .lines()
+            .filter_map(|l| l.strip_prefix("create table if not exists {{schema}}.{{prefix}}"))
+            .map(|l| l.trim_end_matches(|c: char| c == '(' || c.is_whitespace()))
+            .map(|t| {
+                format!(
+                    "drop table if exists {}.{}{} cascade;",
+                    schema_name, table_prefix, t
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    };
+
+    let output = template
+        .replace("{{drop_schema}}", &drop_schema)
+        .replace("{{schema}}", schema_name)
+        .replace("{{prefix}}", table_prefix);
+
+    let dest_path = Path::new(".").join("schema.sql");
+    let mut f = File::create(&dest_path).unwrap();
+    f.write_all(output.as_bytes()).unwrap();
+}
+
 // Create schema_settings.sql file to be loaded into the DB
-fn write_schema_settings_file(frens_file: &FrensFile) {
+fn write_schema_settings_file(
+    organizations: &Vec<FrensFile>,
+    tokens: &Vec<TokenMetadata>,
+    schema_name: &String,
+    table_prefix: &String,
+) {
+    let table = |name: &str| format!("{}.{}{}", schema_name, table_prefix, name);
     let out_dir = ".";
     let dest_path = Path::new(&out_dir).join("schema_settings.sql");
     let mut f = File::create(&dest_path).unwrap();
 
-    let all_addrs = frens_file.all_addresses();
-    let address_lines: Vec<String> = all_addrs
+    let dao_lines: Vec<String> = organizations
         .iter()
-        .enumerate()
-        .map(|(i, v)| {
-            if i == (all_addrs.len() - 1) {
-                format!("('{}')", normalize_address(&v))
-            } else {
-                format!("('{}'),", normalize_address(&v))
-            }
+        .map(|o| {
+            let name: String = match &o.name {
+                Some(n) => n.to_string(),
+                None => o.dao_id(),
+            };
+            format!("({}, {})", sql_string(&o.dao_id()), sql_string(&name))
         })
         .collect();
 
-    let tokens_issued = &frens_file.tokens_issued;
-    let issued_lines: Vec<String> = tokens_issued
-        .iter()
-        .enumerate()
-        .map(|(i, v)| {
+    let mut address_lines: Vec<String> = Vec::new();
+    let mut issued_lines: Vec<String> = Vec::new();
+    let mut metadata_lines: Vec<String> = Vec::new();
+    let mut label_lines: Vec<String> = Vec::new();
+    let mut distributor_lines: Vec<String> = Vec::new();
+
+    for frens_file in organizations.iter() {
+        let dao_id = sql_string(&frens_file.dao_id());
+
+        address_lines.extend(
+            frens_file
+                .all_addresses()
+                .iter()
+                .map(|v| format!("({}, '{}')", dao_id, normalize_address(&v))),
+        );
+
+        issued_lines.extend(frens_file.tokens_issued.iter().map(|v| {
             let token_id: String = match &v.token_id {
                 Some(tid) => tid.to_string(),
                 None => "".to_string(),
             };
-            let l = format!("('{}', {})", normalize_address(&v.address), token_id);
-            if i == (tokens_issued.len() - 1) {
-                return l;
-            }
-            format!("{},", l)
+            format!(
+                "({}, '{}', {})",
+                dao_id,
+                normalize_address(&v.address),
+                sql_string(&token_id)
+            )
+        }));
+
+        metadata_lines.extend(frens_file.treasury_accounts.iter().map(|a| {
+            let group: String = match &a.group {
+                Some(g) => g.to_string(),
+                None => "".to_string(),
+            };
+            let tags: Vec<String> = match &a.tags {
+                Some(t) => t.iter().map(|tag| sql_string(tag)).collect(),
+                None => vec![],
+            };
+            format!(
+                "({}, '{}', {}, {}, array[{}]::text[])",
+                dao_id,
+                normalize_address(&a.address),
+                sql_string(&a.name),
+                sql_string(&group),
+                tags.join(", ")
+            )
+        }));
+
+        // Later entries take precedence: labels files, then address_book, then our own accounts
+        let mut labels: BTreeMap<String, (String, String)> = BTreeMap::new();
+        for label in frens_file.address_labels().iter() {
+            let category: String = match &label.category {
+                Some(c) => c.to_string(),
+                None => "".to_string(),
+            };
+            labels.insert(
+                normalize_address(&label.address),
+                (label.name.to_string(), category),
+            );
+        }
+        for g in frens_file.governance.iter().flatten() {
+            labels.insert(
+                normalize_address(&g.address),
+                (g.name.to_string(), "governor".to_string()),
+            );
+        }
+        for t in frens_file.timelocks.iter().flatten() {
+            labels.insert(
+                normalize_address(&t.address),
+                (t.name.to_string(), "timelock".to_string()),
+            );
+        }
+        for d in frens_file.distributors.iter().flatten() {
+            labels.insert(
+                normalize_address(&d.address),
+                (d.name.to_string(), "distributor".to_string()),
+            );
+        }
+        for t in frens_file.tokens_issued.iter() {
+            labels.insert(
+                normalize_address(&t.address),
+                (t.name.to_string(), "token_issued".to_string()),
+            );
+        }
+        for a in frens_file.treasury_accounts.iter() {
+            labels.insert(
+                normalize_address(&a.address),
+                (a.name.to_string(), "treasury".to_string()),
+            );
+        }
+        label_lines.extend(labels.iter().map(|(address, (name, category))| {
+            format!(
+                "({}, '{}', {}, {})",
+                dao_id,
+                address,
+                sql_string(name),
+                sql_string(category)
+            )
+        }));
+
+        distributor_lines.extend(frens_file.distributors.iter().flatten().map(|d| {
+            let total: String = match &d.total {
+                Some(t) if !t.is_empty() && t.chars().all(|c| c.is_ascii_digit()) => t.to_string(),
+                Some(t) => panic!("Invalid total {} of distributor {}", t, d.name),
+                None => "null".to_string(),
+            };
+            format!(
+                "({}, '{}', {}, '{}', {})",
+                dao_id,
+                normalize_address(&d.address),
+                sql_string(&d.name),
+                normalize_address(&d.token),
+                total
+            )
+        }));
+    }
+
+    // Later entries take precedence: token lists, then the tokens section.  Overrides may set only some fields
+    let mut token_metadata: BTreeMap<String, (String, String, Option<u32>)> = BTreeMap::new();
+    token_metadata.insert(
+        "ETH".to_string(),
+        ("ETH".to_string(), "Ether".to_string(), Some(18)),
+    );
+    for t in tokens.iter() {
+        let entry = token_metadata
+            .entry(normalize_address(&t.address))
+            .or_insert(("".to_string(), "".to_string(), None));
+        if let Some(symbol) = &t.symbol {
+            entry.0 = symbol.to_string();
+        }
+        if let Some(name) = &t.name {
+            entry.1 = name.to_string();
+        }
+        if t.decimals.is_some() {
+            entry.2 = t.decimals;
+        }
+    }
+    let token_lines: Vec<String> = token_metadata
+        .iter()
+        .map(|(address, (symbol, name, decimals))| {
+            let decimals: String = match decimals {
+                Some(d) => d.to_string(),
+                None => "null".to_string(),
+            };
+            format!(
+                "('{}', {}, {}, {})",
+                address,
+                sql_string(symbol),
+                sql_string(name),
+                decimals
+            )
         })
         .collect();
 
-    let output = formatdoc! {"
-        -- @generated
-        begin;
-        insert into substream1.accounts values
+    let mut statements: Vec<String> = Vec::new();
+    statements.push(formatdoc! {"
+        insert into {} (token_address, symbol, name, decimals) values
+        {}
+        on conflict (token_address) do update set symbol = excluded.symbol, name = excluded.name, decimals = excluded.decimals;
+    ", table("tokens"), token_lines.join(",\n")});
+    statements.push(formatdoc! {"
+        insert into {} (dao_id, name) values
+        {}
+        on conflict (dao_id) do update set name = excluded.name;
+    ", table("daos"), dao_lines.join(",\n")});
+    statements.push(formatdoc! {"
+        insert into {} (dao_id, address) values
         {}
         on conflict do nothing;
+    ", table("accounts"), address_lines.join(",\n")});
+    if !issued_lines.is_empty() {
+        statements.push(formatdoc! {"
+            insert into {} (dao_id, token_address, token_id) values
+            {}
+            on conflict do nothing;
+        ", table("tokens_issued"), issued_lines.join(",\n")});
+    }
+    if !metadata_lines.is_empty() {
+        statements.push(formatdoc! {"
+            insert into {} (dao_id, address, name, \"group\", tags) values
+            {}
+            on conflict (dao_id, address) do update set name = excluded.name, \"group\" = excluded.\"group\", tags = excluded.tags;
+        ", table("accounts_metadata"), metadata_lines.join(",\n")});
+    }
+    if !label_lines.is_empty() {
+        statements.push(formatdoc! {"
+            insert into {} (dao_id, address, name, category) values
+            {}
+            on conflict (dao_id, address) do update set name = excluded.name, category = excluded.category;
+        ", table("labels"), label_lines.join(",\n")});
+    }
+    if !distributor_lines.is_empty() {
+        statements.push(formatdoc! {"
+            insert into {} (dao_id, address, name, token_address, total) values
+            {}
+            on conflict (dao_id, address) do update set name = excluded.name, token_address = excluded.token_address, total = excluded.total;
+        ", table("distributors"), distributor_lines.join(",\n")});
+    }
 
-        insert into substream1.tokens_issued values
+    let output = formatdoc! {"
+        -- @generated
+        begin;
         {}
-        on conflict do nothing;
         commit;
-    ",  address_lines.join("\n"), issued_lines.join("\n")
+    ", statements.join("\n")
     };
 
     f.write_all(output.as_bytes()).unwrap();
 }
 
+// Schema names and table prefixes are interpolated into the DDL unquoted
+fn is_sql_identifier(val: &String) -> bool {
+    !val.is_empty()
+        && !val.starts_with(|c: char| c.is_ascii_digit())
+        && val
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+// Quote a string literal for postgres
+fn sql_string(val: &String) -> String {
+    format!("'{}'", val.replace("'", "''"))
+}
+
 fn normalize_address(addr: &String) -> String {
     if addr.starts_with("0x") {
         return addr[2..].to_lowercase();
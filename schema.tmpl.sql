@@ -0,0 +1,1824 @@
+-- Template of the database schema.  Rendered into schema.sql by build.rs, with the schema name and table prefix
+-- from the database section of frens.yaml
+begin;
+
+{{drop_schema}}
+
+create schema if not exists {{schema}};
+
+-- DAOs table:
+create table if not exists {{schema}}.{{prefix}}daos
+(
+    dao_id       text not null constraint {{prefix}}daos_pk primary key,
+    name         text not null default ''
+);
+
+comment on table {{schema}}.{{prefix}}daos is 'organizations indexed by the substream.  Every table carries the dao_id of the
+organization a record belongs to';
+
+-- Accounts table:
+create table if not exists {{schema}}.{{prefix}}accounts
+(
+    dao_id       text not null,
+    address      text not null check (length(address) = 40),
+
+    primary key(dao_id, address)
+);
+
+comment on table {{schema}}.{{prefix}}accounts is 'account addresses to index transactions for';
+
+create table if not exists {{schema}}.{{prefix}}accounts_metadata
+(
+    dao_id       text not null,
+    address      text not null,
+    name         text not null default '',
+    "group"      text not null default '',
+    tags         text[] not null default '{}',
+    discovered_block   bigint,
+    discovered_tx_hash text check (discovered_tx_hash is null or length(discovered_tx_hash) = 64),
+
+    constraint accounts_metadata_fk foreign key(dao_id, address) references {{schema}}.{{prefix}}accounts(dao_id, address) on delete cascade deferrable initially deferred,
+    primary key(dao_id, address)
+);
+
+create index {{prefix}}accounts_metadata_group_idx on {{schema}}.{{prefix}}accounts_metadata(dao_id, "group");
+
+comment on table {{schema}}.{{prefix}}accounts_metadata is 'treasury account names, working group and tags from frens.yaml';
+comment on column {{schema}}.{{prefix}}accounts_metadata."group" is 'working group, eg: operations, grants, payroll.  Empty string if not set';
+comment on column {{schema}}.{{prefix}}accounts_metadata.discovered_block is 'block in which a contract created by a treasury account, a
+configured factory or a discovered account was discovered.  Null for accounts listed in frens.yaml';
+
+-- Address book:
+create table if not exists {{schema}}.{{prefix}}labels
+(
+    dao_id       text not null,
+    address      text not null check (length(address) = 40),
+    name         text not null,
+    category     text not null default '',
+
+    primary key(dao_id, address)
+);
+
+create index {{prefix}}labels_category_idx on {{schema}}.{{prefix}}labels(category);
+
+comment on table {{schema}}.{{prefix}}labels is 'counterparty names from the frens.yaml address_book and labels files';
+comment on column {{schema}}.{{prefix}}labels.category is 'eg: exchange, contributor, vendor, protocol.  treasury and token_issued for our own accounts';
+
+-- Token metadata:
+create table if not exists {{schema}}.{{prefix}}tokens
+(
+    token_address text not null check (length(token_address) = 40 or token_address = 'ETH') constraint {{prefix}}tokens_pk primary key,
+    symbol        text not null default '',
+    name          text not null default '',
+    decimals      integer check (decimals >= 0)
+);
+
+comment on table {{schema}}.{{prefix}}tokens is 'token symbols and decimals from the token lists and tokens section of frens.yaml';
+
+-- Token prices from the Chainlink price feeds:
+create table if not exists {{schema}}.{{prefix}}token_prices
+(
+    token_address text not null check (length(token_address) = 40 or token_address = 'ETH'),
+    block_number  integer not null check (block_number > 0),
+    "timestamp"   integer not null check ("timestamp" > 1436940000),
+    price         numeric not null,
+    feed_address  text not null check (length(feed_address) = 40),
+    round_id      numeric not null,
+    updated_at    bigint not null,
+
+    primary key (token_address, block_number)
+);
+
+comment on table {{schema}}.{{prefix}}token_prices is 'USD price of tokens from the price_feeds of frens.yaml, at every block where the feed was updated';
+comment on column {{schema}}.{{prefix}}token_prices.price is 'last answer of the feed in the block, adjusted by the decimals of the feed';
+comment on column {{schema}}.{{prefix}}token_prices.updated_at is 'timestamp of the round of the feed.  0 when out of range';
+
+create or replace function {{schema}}.{{prefix}}token_price(text, bigint)
+returns numeric
+language plpgsql
+as
+$$
+declare
+   usd numeric;
+begin
+  select tp.price into usd from {{schema}}.{{prefix}}token_prices tp
+      where tp.token_address = $1
+      and tp.block_number <= $2
+      order by block_number desc limit 1;
+  return usd;
+end;
+$$;
+
+comment on function {{schema}}.{{prefix}}token_price(text,bigint) is 'Returns the USD price of a token at block height, from the last feed update.  Null without a feed.
+token_price(token_address, block_number)';
+
+
+create table if not exists {{schema}}.{{prefix}}transfer_counts
+(
+    dao_id        text not null,
+    address       text not null default '' check(address = '' or length(address) = 40),
+    token_address text not null check (length(token_address) = 40 or length(token_address) = 3),
+    token_id      text not null default '',
+
+    tx_count      bigint not null default 0 check(tx_count >= 0),
+
+    primary key (dao_id, token_address, token_id, address)
+);
+
+comment on table {{schema}}.{{prefix}}transfer_counts is 'Keeps track of the number of transactions for each token, for each account';
+
+create index {{prefix}}transfer_counts_address_idx on {{schema}}.{{prefix}}transfer_counts using btree (dao_id, address);
+
+
+-- Transfers table:
+create table if not exists {{schema}}.{{prefix}}token_transfers
+(
+    dao_id       text not null,
+    log_index    integer not null check (log_index >= 0),
+    ordinal      integer not null check (ordinal >= 0),
+    call_index   integer not null check (call_index >= 0),
+    "timestamp"  integer not null check("timestamp" > 1436940000),
+    block_number integer not null check (block_number >= 0),
+    token_address text not null check (length(token_address) = 40),
+    token_id     text not null default '',
+    tx_hash      text not null check (length(tx_hash) = 64),
+    from_address text not null check (length(from_address) = 40),
+    to_address   text not null check (length(to_address) = 40),
+    value        numeric not null check (value >= 0),
+    value_usd    numeric,
+    reason       text not null default '' check (reason in ('', 'rebase')),
+
+    primary key (dao_id, tx_hash, log_index, reason, from_address, to_address)
+);
+
+create index {{prefix}}token_transfers_to_addr_idx on {{schema}}.{{prefix}}token_transfers(to_address);
+create index {{prefix}}token_transfers_from_addr_idx on {{schema}}.{{prefix}}token_transfers(from_address);
+create index {{prefix}}token_transfers_block_num_ordinal_idx on {{schema}}.{{prefix}}token_transfers(block_number, ordinal);
+
+comment on table {{schema}}.{{prefix}}token_transfers is 'Transfers of tokens: ERC-20, ERC-721, ERC-1155';
+comment on column {{schema}}.{{prefix}}token_transfers.token_id is 'token ID for ERC-1155.  Set to empty string '''' for ERC-20 and ERC-721';
+comment on column {{schema}}.{{prefix}}token_transfers.value is 'token value transfered, in the smallest unit of the token';
+comment on column {{schema}}.{{prefix}}token_transfers.reason is 'empty for a Transfer event, rebase for the change of the balance of a rebasing token without one.  A rebase has the log index of the last share event of the transaction and the NULL address as counterparty';
+
+create index if not exists {{prefix}}token_transfers_token_address_token_id_block_numbers on {{schema}}.{{prefix}}token_transfers (token_address, token_id, block_number); --, tx_index);
+
+-- Account balances table:
+create table if not exists {{schema}}.{{prefix}}account_balances
+(
+    dao_id        text not null,
+    block_number  integer not null check (block_number > 0),
+    ordinal       integer not null check (ordinal >= 0),
+    address       text not null,
+    token_address text check (length(token_address) = 3 OR length(token_address) = 40),
+    token_id      text not null default '',
+    balance       numeric not null default 0, --  check (balance >= 0), -- TODO??
+
+    constraint accounts_fk foreign key(dao_id, address) references {{schema}}.{{prefix}}accounts(dao_id, address) on delete restrict,
+    primary key(dao_id, address, token_address, token_id, block_number, ordinal)
+);
+
+create index {{prefix}}token_address_token_id_block_num on {{schema}}.{{prefix}}account_balances (dao_id, token_address, token_id, block_number, ordinal);
+
+comment on column {{schema}}.{{prefix}}account_balances.ordinal is 'Firehose ordinal of the transfer within the block, shared by ETH and token transfers';
+
+-- Running balance on update trigger
+create or replace function {{schema}}.{{prefix}}fn_update_token_account_balances() returns trigger as $$
+begin
+    if exists(select from {{schema}}.{{prefix}}accounts where dao_id = new.dao_id and address = new.to_address) then
+	insert into {{schema}}.{{prefix}}account_balances(dao_id, address, token_address, token_id, block_number, ordinal, balance) values (
+	    new.dao_id,
+	    new.to_address,
+	    new.token_address,
+	    new.token_id,
+	    new.block_number,
+	    new.ordinal,
+	    coalesce(
+		(
+        	select balance from {{schema}}.{{prefix}}account_balances
+		where dao_id = new.dao_id and address = new.to_address
+		and token_address = new.token_address
+		and token_id = new.token_id
+		and ((block_number = new.block_number and ordinal < new.ordinal) or block_number < new.block_number)
+		order by block_number desc, ordinal desc
+		limit 1
+	        ), 0
+	    ) + new.value
+        ) on conflict(dao_id, address, token_address, token_id, block_number, ordinal) do update set balance = excluded.balance;
+
+	insert into {{schema}}.{{prefix}}transfer_counts(dao_id, address, token_address, token_id, tx_count) values (
+		new.dao_id,
+		new.to_address,
+		new.token_address,
+		new.token_id,
+		coalesce(
+			(select tx_count from {{schema}}.{{prefix}}transfer_counts
+				where dao_id = new.dao_id and address = new.to_address
+				and token_address = new.token_address
+				and token_id = new.token_id
+				limit 1
+			), 0) + 1
+	) on conflict(dao_id, token_address, token_id, address) do update set tx_count = excluded.tx_count;
+    end if;
+
+    if exists(select from {{schema}}.{{prefix}}accounts where dao_id = new.dao_id and address = new.from_address) then
+	insert into {{schema}}.{{prefix}}account_balances(dao_id, address, token_address, token_id, block_number, ordinal, balance) values (
+	    new.dao_id,
+	    new.from_address,
+	    new.token_address,
+	    new.token_id,
+	    new.block_number,
+	    new.ordinal,
+	    coalesce(
+		(
+		select balance from {{schema}}.{{prefix}}account_balances
+		where dao_id = new.dao_id and address = new.from_address
+		and token_address = new.token_address
+		and token_id = new.token_id
+		and ((block_number = new.block_number and ordinal < new.ordinal) or block_number < new.block_number)
+		order by block_number desc, ordinal desc
+		limit 1
+		), 0
+	    ) - new.value
+	) on conflict(dao_id, address, token_address, token_id, block_number, ordinal) do update set balance = excluded.balance;
+
+	insert into {{schema}}.{{prefix}}transfer_counts(dao_id, address, token_address, token_id, tx_count) values (
+		new.dao_id,
+		new.from_address,
+		new.token_address,
+		new.token_id,
+		coalesce(
+			(select tx_count from {{schema}}.{{prefix}}transfer_counts
+				where dao_id = new.dao_id and address = new.from_address
+				and token_address = new.token_address
+				and token_id = new.token_id
+				limit 1
+			), 0) + 1
+	) on conflict(dao_id, token_address, token_id, address) do update set tx_count = excluded.tx_count;
+    end if;
+
+    -- update transfer stats for all addresses
+    if exists(select from {{schema}}.{{prefix}}accounts where dao_id = new.dao_id and address = new.from_address) or exists(select from {{schema}}.{{prefix}}accounts where dao_id = new.dao_id and address = new.to_address) then
+	insert into {{schema}}.{{prefix}}transfer_counts(dao_id, token_address, token_id, tx_count) values (
+		new.dao_id,
+		new.token_address,
+		new.token_id,
+		coalesce(
+			(select tx_count from {{schema}}.{{prefix}}transfer_counts
+				where dao_id = new.dao_id and address = ''
+				and token_address = new.token_address
+				and token_id = new.token_id
+				limit 1
+			), 0) + 1
+	) on conflict(dao_id, token_address, token_id, address) do update set tx_count = excluded.tx_count;
+    end if;
+
+    return new;
+end;
+$$ language plpgsql;
+
+create or replace trigger tr_update_token_account_balances
+after insert on {{schema}}.{{prefix}}token_transfers for each row
+    execute procedure {{schema}}.{{prefix}}fn_update_token_account_balances();
+
+
+-- Balance functions:
+create or replace function {{schema}}.{{prefix}}account_balance(text, text, text, text, bigint)
+returns numeric
+language plpgsql
+as
+$$
+declare
+   bal numeric;
+begin
+  select balance into bal from {{schema}}.{{prefix}}account_balances ab
+      where ab.dao_id = $1
+      and ab.address = $2
+      and ab.token_address = $3
+      and ab.token_id = $4
+      and ab.block_number <= $5
+      order by block_number desc, ordinal desc limit 1;
+  return coalesce(bal,0);
+end;
+$$;
+
+comment on function {{schema}}.{{prefix}}account_balance(text,text,text,text,bigint) is 'Returns account balance at block height.
+account_balance(dao_id, address, token_address, token_id, block_number)';
+
+create or replace function {{schema}}.{{prefix}}account_balance(text, text, text, text, bigint, int)
+returns numeric
+language plpgsql
+as
+$$
+declare
+   bal numeric;
+begin
+  select balance into bal from {{schema}}.{{prefix}}account_balances ab
+      where ab.dao_id = $1
+      and ab.address = $2
+      and ab.token_address = $3
+      and ab.token_id = $4
+      and (
+	ab.block_number <= $5
+	or (
+	  ab.block_number = $5 and ab.ordinal <= $6
+        )
+      )
+      order by block_number desc, ordinal desc limit 1;
+  return coalesce(bal,0);
+end;
+$$;
+
+--comment on function {{schema}}.{{prefix}}account_balance is 'Returns account balance at block height.
+--account_balance(dao_id, address, token_address, token_id, block_number)';
+
+create or replace function {{schema}}.{{prefix}}all_accounts_balance(text, text, text, bigint)
+returns numeric
+language plpgsql
+as
+$$
+declare
+   bal numeric;
+begin
+  select  sum(bal.account_balance) into bal from {{schema}}.{{prefix}}accounts a,
+  lateral (select account_balance from {{schema}}.{{prefix}}account_balance(a.dao_id, a.address, $2, $3, $4)) bal
+  where a.dao_id = $1;
+  return coalesce(bal,0);
+end;
+$$;
+
+comment on function {{schema}}.{{prefix}}all_accounts_balance(text,text,text,bigint) is 'Returns sum of all accounts balance at block height.
+all_accounts_balance(dao_id, token_address, token_id, block_number)';
+
+create or replace function {{schema}}.{{prefix}}all_accounts_balance(text, text, text, bigint, int)
+returns numeric
+language plpgsql
+as
+$$
+declare
+   bal numeric;
+begin
+  select  sum(bal.account_balance) into bal from {{schema}}.{{prefix}}accounts a,
+  lateral (select account_balance from {{schema}}.{{prefix}}account_balance(a.dao_id, a.address, $2, $3, $4, $5)) bal
+  where a.dao_id = $1;
+  return coalesce(bal,0);
+end;
+$$;
+
+comment on function {{schema}}.{{prefix}}all_accounts_balance(text,text,text,bigint,int) is 'Returns sum of all accounts balance at block height.
+all_accounts_balance(dao_id, token_address, token_id, block_number)';
+
+create or replace function {{schema}}.{{prefix}}group_balance(text, text, text, text, bigint)
+returns numeric
+language plpgsql
+as
+$$
+declare
+   bal numeric;
+begin
+  select  sum(bal.account_balance) into bal from {{schema}}.{{prefix}}accounts_metadata m,
+  lateral (select account_balance from {{schema}}.{{prefix}}account_balance(m.dao_id, m.address, $3, $4, $5)) bal
+  where m.dao_id = $1 and m."group" = $2;
+  return coalesce(bal,0);
+end;
+$$;
+
+comment on function {{schema}}.{{prefix}}group_balance(text,text,text,text,bigint) is 'Returns sum of balances of the accounts in a group at block height.
+group_balance(dao_id, group, token_address, token_id, block_number)';
+
+-- Value transfers:
+create table if not exists {{schema}}.{{prefix}}value_transfers
+(
+    dao_id       text not null,
+    reason       integer not null check (reason >= 0 and reason <= 16),
+    call_index   integer not null check (call_index >= 0),
+    tx_index     integer not null check (tx_index >= 0),
+    ordinal      integer not null check (ordinal >= 0),
+    "timestamp"  integer not null check("timestamp" > 1436940000),
+    block_number integer not null check (block_number > 0),
+    value        numeric not null check (value >= 0),
+    value_usd    numeric,
+    hash      text not null check(length(hash) = 64),
+    from_address text not null check (length(from_address) = 0 or length(from_address) = 40),
+    to_address   text not null check (length(to_address) = 0 or length(to_address) = 40),
+
+    check ( not (length(to_address) = 0 and length(from_address) = 0)),
+    primary key(dao_id, hash, ordinal)
+);
+
+create index {{prefix}}value_transfers_to_addr_idx on {{schema}}.{{prefix}}value_transfers(to_address);
+create index {{prefix}}value_transfers_from_addr_idx on {{schema}}.{{prefix}}value_transfers(from_address);
+create index {{prefix}}value_transfers_block_num_ordinal_idx on {{schema}}.{{prefix}}value_transfers(block_number, ordinal);
+
+comment on column {{schema}}.{{prefix}}value_transfers.hash is 'transaction or block hash, depending on reason';
+comment on column {{schema}}.{{prefix}}value_transfers.reason is 'substreams_ethereum::pb::eth::v2::balance_change::Reason';
+
+create index if not exists {{prefix}}value_transfers_block_number on {{schema}}.{{prefix}}value_transfers(block_number);
+
+
+
+create or replace function {{schema}}.{{prefix}}fn_update_value_account_balances() returns trigger as $$
+begin
+    if exists(select from {{schema}}.{{prefix}}accounts where dao_id = new.dao_id and address = new.to_address) then
+	insert into {{schema}}.{{prefix}}account_balances(dao_id, address, token_address, block_number, ordinal, balance) values (
+	    new.dao_id,
+	    new.to_address,
+	    'ETH',
+	    new.block_number,
+	    new.ordinal,
+	    coalesce(
+		(
+        	select balance from {{schema}}.{{prefix}}account_balances
+		where dao_id = new.dao_id and address = new.to_address
+		and token_address = 'ETH'
+		and token_id = ''
+		and ((block_number = new.block_number and ordinal < new.ordinal) or block_number < new.block_number)
+		order by block_number desc, ordinal desc
+		limit 1
+	        ), 0
+	    ) + new.value
+        ) on conflict(dao_id, address, token_address, token_id, block_number, ordinal) do update set balance = excluded.balance;
+
+	insert into {{schema}}.{{prefix}}transfer_counts(dao_id, address, token_address, token_id, tx_count) values (
+		new.dao_id,
+		new.to_address,
+		'ETH',
+		'',
+		coalesce(
+			(select tx_count from {{schema}}.{{prefix}}transfer_counts
+				where dao_id = new.dao_id and address = new.to_address
+				and token_address = 'ETH'
+				and token_id = ''
+				limit 1
+			), 0) + 1
+	) on conflict(dao_id, token_address, token_id, address) do update set tx_count = excluded.tx_count;
+    end if;
+
+    if exists(select from {{schema}}.{{prefix}}accounts where dao_id = new.dao_id and address = new.from_address) then
+	insert into {{schema}}.{{prefix}}account_balances(dao_id, address, token_address, block_number, ordinal, balance) values (
+	    new.dao_id,
+	    new.from_address,
+	    'ETH',
+	    new.block_number,
+	    new.ordinal,
+	    coalesce(
+		(
+		select balance from {{schema}}.{{prefix}}account_balances
+		where dao_id = new.dao_id and address = new.from_address
+		and token_address = 'ETH'
+		and token_id = ''
+		and ((block_number = new.block_number and ordinal < new.ordinal) or block_number < new.block_number)
+		order by block_number desc, ordinal desc
+		limit 1
+		), 0
+	    ) - new.value
+	) on conflict(dao_id, address, token_address, token_id, block_number, ordinal) do update set balance = excluded.balance;
+
+	insert into {{schema}}.{{prefix}}transfer_counts(dao_id, address, token_address, token_id, tx_count) values (
+		new.dao_id,
+		new.from_address,
+		'ETH',
+		'',
+		coalesce(
+			(select tx_count from {{schema}}.{{prefix}}transfer_counts
+				where dao_id = new.dao_id and address = new.from_address
+				and token_address = 'ETH'
+				and token_id = ''
+				limit 1
+			), 0) + 1
+	) on conflict(dao_id, token_address, token_id, address) do update set tx_count = excluded.tx_count;
+    end if;
+
+    -- update transfer stats for all addresses
+    if exists(select from {{schema}}.{{prefix}}accounts where dao_id = new.dao_id and address = new.from_address) or exists(select from {{schema}}.{{prefix}}accounts where dao_id = new.dao_id and address = new.to_address) then
+	insert into {{schema}}.{{prefix}}transfer_counts(dao_id, token_address, token_id, tx_count) values (
+		new.dao_id,
+		'ETH',
+		'',
+		coalesce(
+			(select tx_count from {{schema}}.{{prefix}}transfer_counts
+				where dao_id = new.dao_id and address = ''
+				and token_address = 'ETH'
+				and token_id = ''
+				limit 1
+			), 0) + 1
+	) on conflict(dao_id, token_address, token_id, address) do update set tx_count = excluded.tx_count;
+    end if;
+
+    return new;
+end;
+$$ language plpgsql;
+
+create or replace trigger tr_update_value_account_balances
+after insert on {{schema}}.{{prefix}}value_transfers for each row
+    execute procedure {{schema}}.{{prefix}}fn_update_value_account_balances();
+
+-- Call Traces:
+create table if not exists {{schema}}.{{prefix}}call_traces
+(
+    dao_id     text not null,
+    "index"    integer not null check ("index" >= 0),
+    tx_hash    text not null check (length(tx_hash) = 64),
+    trace      jsonb not null,
+
+    -- foreign key to value_transfer?
+    primary key(dao_id, tx_hash, "index")
+);
+
+comment on table {{schema}}.{{prefix}}call_traces is 'Stores records of a JSON representation of the call stack
+of every transaction which produced a value transfer or a token transfer';
+
+-- Transfers View:
+create or replace view {{schema}}.{{prefix}}transfers
+as
+    select
+    t.*,
+    coalesce(fl.name, '') as from_label,
+    coalesce(fl.category, '') as from_category,
+    coalesce(tl.name, '') as to_label,
+    coalesce(tl.category, '') as to_category,
+    coalesce(tk.symbol, '') as symbol,
+    t."value" / power(10::numeric, tk.decimals) as amount
+    from (
+	(
+	    select
+	    dao_id,
+	    ordinal,
+	    "timestamp",
+	    block_number,
+	    "value",
+	    value_usd,
+	    token_address,
+	    token_id,
+	    tx_hash as hash,
+	    from_address,
+	    to_address
+	    from {{schema}}.{{prefix}}token_transfers
+	)
+	union all
+	(
+	    select
+	    dao_id,
+	    ordinal,
+	    "timestamp",
+	    block_number,
+	    "value",
+	    value_usd,
+	    'ETH' as token_address,
+	    '' as token_id,
+	    hash,
+	    from_address,
+	    to_address
+	    from {{schema}}.{{prefix}}value_transfers
+	)
+    ) t
+    left join {{schema}}.{{prefix}}labels fl on fl.dao_id = t.dao_id and fl.address = t.from_address
+    left join {{schema}}.{{prefix}}labels tl on tl.dao_id = t.dao_id and tl.address = t.to_address
+    left join {{schema}}.{{prefix}}tokens tk on tk.token_address = t.token_address;
+
+comment on view {{schema}}.{{prefix}}transfers is 'Token and value transfers, with counterparty labels from the address book';
+comment on column {{schema}}.{{prefix}}transfers.amount is 'value adjusted by the token decimals.  Null for tokens without metadata';
+comment on column {{schema}}.{{prefix}}transfers.value_usd is 'USD value at the time of the transfer, from the price pools.  Null for tokens without a price';
+
+-- Transfers by account group:
+create or replace view {{schema}}.{{prefix}}group_transfers
+as
+    select
+    m.dao_id,
+    m."group",
+    case when m.address = t.to_address then 'in' else 'out' end as direction,
+    m.address,
+    t.ordinal,
+    t."timestamp",
+    t.block_number,
+    t."value",
+    t.amount,
+    t.value_usd,
+    t.symbol,
+    t.token_address,
+    t.token_id,
+    t.hash,
+    t.from_address,
+    t.to_address
+    from {{schema}}.{{prefix}}transfers t
+    join {{schema}}.{{prefix}}accounts_metadata m on m.dao_id = t.dao_id and (m.address = t.to_address or m.address = t.from_address)
+    where m."group" <> '';
+
+comment on view {{schema}}.{{prefix}}group_transfers is 'Transfers in and out of each account group.  Transfers between
+two accounts of the same group appear once in each direction';
+
+
+-- Net worth:
+create table if not exists {{schema}}.{{prefix}}net_worth
+(
+    dao_id        text not null,
+    day           integer not null,
+    "date"        date generated always as (date '1970-01-01' + day) stored,
+    address       text not null check (length(address) = 40),
+    token_address text not null check (length(token_address) = 40 or token_address = 'ETH'),
+    block_number  integer not null check (block_number > 0),
+    "timestamp"   integer not null check ("timestamp" > 1436940000),
+    balance       numeric not null,
+    value_usd     numeric not null,
+
+    primary key (dao_id, day, address, token_address)
+);
+
+comment on table {{schema}}.{{prefix}}net_worth is 'Daily snapshots of the priced token balances of each account, taken at the first block of the day';
+comment on column {{schema}}.{{prefix}}net_worth.day is 'days since the Unix epoch';
+comment on column {{schema}}.{{prefix}}net_worth.balance is 'balance in the smallest unit of the token';
+
+create or replace view {{schema}}.{{prefix}}account_net_worth
+as
+    select
+    n.dao_id,
+    n."date",
+    n.address,
+    coalesce(m.name, '') as name,
+    coalesce(m."group", '') as "group",
+    sum(n.value_usd) as value_usd
+    from {{schema}}.{{prefix}}net_worth n
+    left join {{schema}}.{{prefix}}accounts_metadata m on m.dao_id = n.dao_id and m.address = n.address
+    group by n.dao_id, n."date", n.address, m.name, m."group";
+
+create or replace view {{schema}}.{{prefix}}group_net_worth
+as
+    select
+    dao_id,
+    "date",
+    "group",
+    sum(value_usd) as value_usd
+    from {{schema}}.{{prefix}}account_net_worth
+    where "group" <> ''
+    group by dao_id, "date", "group";
+
+create or replace view {{schema}}.{{prefix}}dao_net_worth
+as
+    select
+    dao_id,
+    "date",
+    sum(value_usd) as value_usd
+    from {{schema}}.{{prefix}}net_worth
+    group by dao_id, "date";
+
+comment on view {{schema}}.{{prefix}}dao_net_worth is 'Total USD value of the priced tokens of the DAO accounts, by day';
+
+
+-- Period snapshots:
+create table if not exists {{schema}}.{{prefix}}daily_balances
+(
+    dao_id          text not null,
+    start_date      date not null,
+    address         text not null check (length(address) = 40),
+    token_address   text not null check (length(token_address) = 40 or token_address = 'ETH'),
+    opening_balance numeric not null,
+    inflow          numeric not null default 0 check (inflow >= 0),
+    outflow         numeric not null default 0 check (outflow >= 0),
+    fees            numeric not null default 0,
+    closing_balance numeric not null,
+    block_number    integer not null check (block_number > 0),
+    "timestamp"     integer not null check ("timestamp" > 1436940000),
+
+    primary key (dao_id, start_date, address, token_address)
+);
+
+comment on table {{schema}}.{{prefix}}daily_balances is 'Opening and closing balance, inflows, outflows and fees of each account and token by UTC day.
+Only days with transfers have a row; the balance of other days is the closing balance of the previous row';
+comment on column {{schema}}.{{prefix}}daily_balances.fees is 'ETH gas paid, net of refunds, in wei';
+comment on column {{schema}}.{{prefix}}daily_balances.block_number is 'last block of the day with transfers';
+
+create table if not exists {{schema}}.{{prefix}}monthly_balances
+(
+    dao_id          text not null,
+    start_date      date not null,
+    address         text not null check (length(address) = 40),
+    token_address   text not null check (length(token_address) = 40 or token_address = 'ETH'),
+    opening_balance numeric not null,
+    inflow          numeric not null default 0 check (inflow >= 0),
+    outflow         numeric not null default 0 check (outflow >= 0),
+    fees            numeric not null default 0,
+    closing_balance numeric not null,
+    block_number    integer not null check (block_number > 0),
+    "timestamp"     integer not null check ("timestamp" > 1436940000),
+
+    primary key (dao_id, start_date, address, token_address)
+);
+
+comment on table {{schema}}.{{prefix}}monthly_balances is 'Opening and closing balance, inflows, outflows and fees of each account and token by UTC month.
+Only months with transfers have a row; the balance of other months is the closing balance of the previous row';
+comment on column {{schema}}.{{prefix}}monthly_balances.fees is 'ETH gas paid, net of refunds, in wei';
+comment on column {{schema}}.{{prefix}}monthly_balances.block_number is 'last block of the month with transfers';
+
+
+-- Tokens Issued:
+create table if not exists {{schema}}.{{prefix}}tokens_issued
+(
+    dao_id        text not null,
+    token_address text not null check (length(token_address) = 40),
+    token_id      text not null default '',
+
+    primary key(dao_id, token_address, token_id)
+);
+
+create table if not exists {{schema}}.{{prefix}}tokens_issued_stats
+(
+    dao_id        text not null,
+    token_holders bigint  not null default 0 check(token_holders >= 0),
+    tx_count      bigint  not null default 0 check(tx_count >= 0),
+
+    minted        numeric not null default 0,
+    burned        numeric not null default 0,
+
+    block_number  integer not null check (block_number >= 0),
+
+    token_address text not null check (length(token_address) = 40),
+    token_id      text not null default '',
+
+    total_supply  numeric not null default 0 check(total_supply >= 0),
+
+    primary key (dao_id, token_address, token_id, block_number)
+);
+
+create table if not exists {{schema}}.{{prefix}}tokens_issued_transfers (
+    dao_id       text not null,
+    log_index    int not null check (log_index >= 0),
+    ordinal      int not null check (ordinal >= 0),
+    call_index   int not null check (call_index >= 0),
+    "timestamp"  integer not null check("timestamp" > 1436940000),
+    block_number integer not null check (block_number >= 0),
+    token_address text check (length(token_address) = 0 or length(token_address) = 40),
+    token_id      text check ((length(token_address) = 0 and length(token_id) = 0) or length(token_address) = 40),
+    tx_hash      text not null check (length(tx_hash) = 64),
+    from_address text not null check (length(from_address) = 40),
+    to_address   text not null check (length(to_address) = 40),
+    value        numeric not null check (value >= 0),
+    value_usd    numeric,
+
+    -- TODO: foreign key to tokens_issued
+    primary key (dao_id, tx_hash, log_index)
+);
+
+create index {{prefix}}tokens_issued_transfers_block_num_log_idx on {{schema}}.{{prefix}}tokens_issued_transfers(dao_id, token_address, token_id, block_number, ordinal);
+
+
+
+-- Running balance on insert trigger
+create or replace function {{schema}}.{{prefix}}fn_update_issued_tokens_transfered() returns trigger as $$
+declare
+    prev_to_bal   numeric;
+    prev_from_bal numeric;
+    prev_token_holders_count bigint;
+    token_holder_count_changed boolean;
+begin
+   prev_to_bal = coalesce(
+	    (select balance from {{schema}}.{{prefix}}tokens_issued_holder_balances
+	    where dao_id = new.dao_id and address = new.to_address
+	    and token_address = new.token_address
+	    and token_id = new.token_id
+	    order by block_number desc
+	    limit 1
+	    ), 0);
+
+    prev_from_bal = coalesce(
+	    (select balance from {{schema}}.{{prefix}}tokens_issued_holder_balances
+	    where dao_id = new.dao_id and address = new.from_address
+	    and token_address = new.token_address
+	    and token_id = new.token_id
+	    order by block_number desc
+	    limit 1
+	    ), 0);
+
+    prev_token_holders_count = coalesce(
+	(select token_holders from {{schema}}.{{prefix}}tokens_issued_stats
+	where dao_id = new.dao_id and token_address = new.token_address
+	and token_id = new.token_id
+	order by block_number desc
+	limit 1
+        ), 0);
+
+    token_holder_count_changed = false;
+    if (prev_to_bal = 0 and new.value > 0) then
+	prev_token_holders_count = prev_token_holders_count + 1;
+    	token_holder_count_changed = true;
+    end if;
+
+    if ((prev_from_bal - new.value) = 0) then
+	prev_token_holders_count = prev_token_holders_count - 1;
+    	token_holder_count_changed = true;
+    end if;
+
+    -- update balance history:
+    insert into {{schema}}.{{prefix}}tokens_issued_holder_balances(dao_id, block_number, address, token_address, token_id, balance) values (
+	new.dao_id,
+	new.block_number,
+	new.to_address,
+	new.token_address,
+	new.token_id,
+	prev_to_bal + new.value
+    ) on conflict(dao_id, address, token_address, token_id, block_number) do update set balance = excluded.balance;
+    insert into {{schema}}.{{prefix}}tokens_issued_holder_balances(dao_id, block_number, address, token_address, token_id, balance) values (
+	new.dao_id,
+	new.block_number,
+	new.from_address,
+	new.token_address,
+	new.token_id,
+	prev_from_bal - new.value
+    ) on conflict(dao_id, address, token_address, token_id, block_number) do update set balance = excluded.balance;
+
+    -- update holders list
+    insert into {{schema}}.{{prefix}}tokens_issued_holders(dao_id, address, token_address, token_id, balance) values (
+	new.dao_id,
+	new.to_address,
+	new.token_address,
+	new.token_id,
+	coalesce(
+		(select balance from {{schema}}.{{prefix}}tokens_issued_holders
+			where dao_id = new.dao_id and address = new.to_address
+			and token_address = new.token_address
+			and token_id = new.token_id
+			limit 1
+		), 0) + new.value
+    ) on conflict(dao_id, address, token_address, token_id) do update set balance = excluded.balance;
+
+    insert into {{schema}}.{{prefix}}tokens_issued_holders(dao_id, address, token_address, token_id, balance) values (
+	new.dao_id,
+	new.from_address,
+	new.token_address,
+	new.token_id,
+	coalesce(
+		(select balance from {{schema}}.{{prefix}}tokens_issued_holders
+			where dao_id = new.dao_id and address = new.from_address
+			and token_address = new.token_address
+			and token_id = new.token_id
+			limit 1
+		), 0) - new.value
+    ) on conflict(dao_id, address, token_address, token_id) do update set balance = excluded.balance;
+
+    -- check for burns/mints
+    -- mint:
+    if ((select {{schema}}.{{prefix}}is_burn_address(new.from_address)) and not (select {{schema}}.{{prefix}}is_burn_address(new.to_address))) then
+        insert into {{schema}}.{{prefix}}tokens_issued_stats(dao_id, token_address, token_id, block_number, total_supply, token_holders, tx_count, minted, burned) values (
+            new.dao_id,
+            new.token_address,
+            new.token_id,
+	    new.block_number,
+            coalesce((select total_supply from {{schema}}.{{prefix}}tokens_issued_stats where dao_id = new.dao_id and token_address = new.token_address and token_id = new.token_id order by block_number desc limit 1), 0) + new.value,
+            prev_token_holders_count,
+	    coalesce((select tx_count from {{schema}}.{{prefix}}tokens_issued_stats where dao_id = new.dao_id and token_address = new.token_address and token_id = new.token_id order by block_number desc limit 1), 0) + 1,
+	    coalesce((select minted from {{schema}}.{{prefix}}tokens_issued_stats where dao_id = new.dao_id and token_address = new.token_address and token_id = new.token_id order by block_number desc limit 1), 0) + new.value,
+	    coalesce((select burned from {{schema}}.{{prefix}}tokens_issued_stats where dao_id = new.dao_id and token_address = new.token_address and token_id = new.token_id order by block_number desc limit 1), 0)
+        ) on conflict(dao_id, token_address, token_id, block_number) do update set total_supply = excluded.total_supply, token_holders = excluded.token_holders, tx_count = excluded.tx_count, minted = excluded.minted;
+
+    -- burn:
+    elsif ((select {{schema}}.{{prefix}}is_burn_address(new.to_address)) and not (select {{schema}}.{{prefix}}is_burn_address(new.from_address))) then
+        insert into {{schema}}.{{prefix}}tokens_issued_stats(dao_id, token_address, token_id, block_number, total_supply, token_holders, tx_count, minted, burned) values (
+            new.dao_id,
+            new.token_address,
+            new.token_id,
+	    new.block_number,
+            coalesce((select total_supply from {{schema}}.{{prefix}}tokens_issued_stats where dao_id = new.dao_id and token_address = new.token_address and token_id = new.token_id order by block_number desc limit 1), 0) - new.value,
+            prev_token_holders_count,
+	    coalesce((select tx_count from {{schema}}.{{prefix}}tokens_issued_stats where dao_id = new.dao_id and token_address = new.token_address and token_id = new.token_id order by block_number desc limit 1), 0) + 1,
+	    coalesce((select minted from {{schema}}.{{prefix}}tokens_issued_stats where dao_id = new.dao_id and token_address = new.token_address and token_id = new.token_id order by block_number desc limit 1), 0),
+	    coalesce((select burned from {{schema}}.{{prefix}}tokens_issued_stats where dao_id = new.dao_id and token_address = new.token_address and token_id = new.token_id order by block_number desc limit 1), 0) - new.value
+        ) on conflict(dao_id, token_address, token_id, block_number) do update set total_supply = excluded.total_supply, token_holders = excluded.token_holders, tx_count = excluded.tx_count, burned = excluded.burned;
+    elsif token_holder_count_changed then
+	-- regular transfer:
+        insert into {{schema}}.{{prefix}}tokens_issued_stats(dao_id, token_address, token_id, block_number, total_supply, token_holders, tx_count, minted, burned) values (
+            new.dao_id,
+            new.token_address,
+            new.token_id,
+	    new.block_number,
+            (select total_supply from {{schema}}.{{prefix}}tokens_issued_stats where dao_id = new.dao_id and token_address = new.token_address and token_id = new.token_id order by block_number desc limit 1),
+            prev_token_holders_count,
+	    coalesce((select tx_count from {{schema}}.{{prefix}}tokens_issued_stats where dao_id = new.dao_id and token_address = new.token_address and token_id = new.token_id order by block_number desc limit 1), 0) + 1,
+	    coalesce((select minted from {{schema}}.{{prefix}}tokens_issued_stats where dao_id = new.dao_id and token_address = new.token_address and token_id = new.token_id order by block_number desc limit 1), 0),
+	    coalesce((select burned from {{schema}}.{{prefix}}tokens_issued_stats where dao_id = new.dao_id and token_address = new.token_address and token_id = new.token_id order by block_number desc limit 1), 0)
+        ) on conflict(dao_id, token_address, token_id, block_number) do update set total_supply = excluded.total_supply, token_holders = excluded.token_holders, tx_count = excluded.tx_count;
+    end if;
+
+    return new;
+end;
+$$ language plpgsql;
+
+create or replace trigger tr_update_issued_tokens_transfered
+after insert on {{schema}}.{{prefix}}tokens_issued_transfers for each row
+    execute procedure {{schema}}.{{prefix}}fn_update_issued_tokens_transfered();
+
+
+-- Issued Token holders:
+create table if not exists {{schema}}.{{prefix}}tokens_issued_holder_balances
+(
+    dao_id        text not null,
+    block_number  integer not null check (block_number > 0),
+    address       text not null check (length(address) = 40),
+    token_address text check (length(token_address) = 3 OR length(token_address) = 40),
+    token_id      text not null default '',
+    balance       numeric not null default 0,
+
+    primary key(dao_id, address, token_address, token_id, block_number)
+);
+
+comment on table {{schema}}.{{prefix}}tokens_issued_holder_balances is 'Balance change history for tokens issued';
+
+create table if not exists {{schema}}.{{prefix}}tokens_issued_holders
+(
+    dao_id        text not null,
+    address       text not null check (length(address) = 40),
+    token_address text check (length(token_address) = 3 OR length(token_address) = 40),
+    token_id      text not null default '',
+    balance       numeric not null default 0,
+
+    primary key(dao_id, address, token_address, token_id)
+);
+
+comment on table {{schema}}.{{prefix}}tokens_issued_holders is 'Issued token holders and current balance';
+
+create index {{prefix}}tokens_issued_holders_balances on {{schema}}.{{prefix}}tokens_issued_holders(dao_id, token_address, token_id, balance);
+
+
+-- Delegation of the issued ERC20Votes tokens:
+create table if not exists {{schema}}.{{prefix}}delegate_changes
+(
+    dao_id        text not null,
+    token_address text not null check (length(token_address) = 40),
+    tx_hash       text not null check (length(tx_hash) = 64),
+    log_index     integer not null check (log_index >= 0),
+    ordinal       integer not null check (ordinal >= 0),
+    block_number  integer not null check (block_number > 0),
+    "timestamp"   integer not null check ("timestamp" > 1436940000),
+    delegator     text not null check (length(delegator) = 40),
+    from_delegate text not null check (length(from_delegate) = 40),
+    to_delegate   text not null check (length(to_delegate) = 40),
+
+    primary key (dao_id, tx_hash, log_index)
+);
+
+create index {{prefix}}delegate_changes_delegator_idx on {{schema}}.{{prefix}}delegate_changes(dao_id, token_address, delegator, block_number, ordinal);
+create index {{prefix}}delegate_changes_to_delegate_idx on {{schema}}.{{prefix}}delegate_changes(dao_id, token_address, to_delegate);
+
+comment on table {{schema}}.{{prefix}}delegate_changes is 'DelegateChanged events of the issued tokens: history of the delegate chosen by each delegator';
+comment on column {{schema}}.{{prefix}}delegate_changes.to_delegate is 'new delegate, 0000000000000000000000000000000000000000 when the delegation is removed';
+
+create table if not exists {{schema}}.{{prefix}}voting_power
+(
+    dao_id        text not null,
+    token_address text not null check (length(token_address) = 40),
+    delegate      text not null check (length(delegate) = 40),
+    block_number  integer not null check (block_number > 0),
+    "timestamp"   integer not null check ("timestamp" > 1436940000),
+    votes         numeric not null check (votes >= 0),
+    tx_hash       text not null check (length(tx_hash) = 64),
+
+    primary key (dao_id, token_address, delegate, block_number)
+);
+
+create index {{prefix}}voting_power_block_number_idx on {{schema}}.{{prefix}}voting_power(dao_id, token_address, block_number);
+
+comment on table {{schema}}.{{prefix}}voting_power is 'Votes of each delegate of the issued tokens, at every block where they changed, from the DelegateVotesChanged events';
+comment on column {{schema}}.{{prefix}}voting_power.votes is 'votes at the end of the block, in the smallest unit of the token';
+
+create or replace view {{schema}}.{{prefix}}current_delegates as
+    select distinct on (dao_id, token_address, delegator)
+        dao_id, token_address, delegator, to_delegate as delegate, block_number, "timestamp"
+    from {{schema}}.{{prefix}}delegate_changes
+    order by dao_id, token_address, delegator, block_number desc, ordinal desc;
+
+comment on view {{schema}}.{{prefix}}current_delegates is 'Current delegate of each delegator of the issued tokens';
+
+create or replace view {{schema}}.{{prefix}}current_voting_power as
+    select * from (
+        select distinct on (dao_id, token_address, delegate)
+            dao_id, token_address, delegate, votes, block_number, "timestamp"
+        from {{schema}}.{{prefix}}voting_power
+        order by dao_id, token_address, delegate, block_number desc
+    ) vp
+    where votes > 0;
+
+comment on view {{schema}}.{{prefix}}current_voting_power is 'Current votes of the delegates of the issued tokens which hold voting power';
+
+create or replace function {{schema}}.{{prefix}}delegate_of(text, text, text, bigint)
+returns text
+language plpgsql
+as
+$$
+declare
+   result text;
+begin
+  select dc.to_delegate into result from {{schema}}.{{prefix}}delegate_changes dc
+      where dc.dao_id = $1
+      and dc.token_address = $2
+      and dc.delegator = $3
+      and dc.block_number <= $4
+      order by dc.block_number desc, dc.ordinal desc limit 1;
+  return result;
+end;
+$$;
+
+comment on function {{schema}}.{{prefix}}delegate_of(text,text,text,bigint) is 'Returns the delegate of a delegator at block height.  Null before the first delegation.
+delegate_of(dao_id, token_address, delegator, block_number)';
+
+create or replace function {{schema}}.{{prefix}}votes_at(text, text, text, bigint)
+returns numeric
+language plpgsql
+as
+$$
+declare
+   result numeric;
+begin
+  select vp.votes into result from {{schema}}.{{prefix}}voting_power vp
+      where vp.dao_id = $1
+      and vp.token_address = $2
+      and vp.delegate = $3
+      and vp.block_number <= $4
+      order by vp.block_number desc limit 1;
+  return coalesce(result, 0);
+end;
+$$;
+
+comment on function {{schema}}.{{prefix}}votes_at(text,text,text,bigint) is 'Returns the votes of a delegate at block height.
+votes_at(dao_id, token_address, delegate, block_number)';
+
+
+-- Governor proposals and votes:
+create table if not exists {{schema}}.{{prefix}}proposals
+(
+    dao_id           text not null,
+    governor_address text not null check (length(governor_address) = 40),
+    proposal_id      numeric not null,
+    block_number     integer not null check (block_number > 0),
+    "timestamp"      integer not null check ("timestamp" > 1436940000),
+    tx_hash          text not null check (length(tx_hash) = 64),
+    log_index        integer not null check (log_index >= 0),
+    proposer         text not null check (length(proposer) = 40),
+    vote_start       numeric not null,
+    vote_end         numeric not null,
+    description      text not null default '',
+
+    primary key (dao_id, governor_address, proposal_id)
+);
+
+comment on table {{schema}}.{{prefix}}proposals is 'ProposalCreated events of the governors in the governance section of frens.yaml';
+comment on column {{schema}}.{{prefix}}proposals.vote_start is 'block, or timestamp for governors with a timestamp clock, at which voting starts';
+
+create table if not exists {{schema}}.{{prefix}}proposal_actions
+(
+    dao_id           text not null,
+    governor_address text not null check (length(governor_address) = 40),
+    proposal_id      numeric not null,
+    action_index     integer not null check (action_index >= 0),
+    target           text not null check (length(target) = 40),
+    "value"          numeric not null check ("value" >= 0),
+    signature        text not null default '',
+    calldata         text not null default '',
+
+    constraint proposals_fk foreign key(dao_id, governor_address, proposal_id) references {{schema}}.{{prefix}}proposals(dao_id, governor_address, proposal_id) on delete cascade,
+    primary key (dao_id, governor_address, proposal_id, action_index)
+);
+
+comment on table {{schema}}.{{prefix}}proposal_actions is 'Calls made by a proposal when executed';
+comment on column {{schema}}.{{prefix}}proposal_actions.signature is 'function signature, empty when the calldata includes the selector';
+
+create table if not exists {{schema}}.{{prefix}}votes
+(
+    dao_id           text not null,
+    governor_address text not null check (length(governor_address) = 40),
+    proposal_id      numeric not null,
+    tx_hash          text not null check (length(tx_hash) = 64),
+    log_index        integer not null check (log_index >= 0),
+    block_number     integer not null check (block_number > 0),
+    "timestamp"      integer not null check ("timestamp" > 1436940000),
+    voter            text not null check (length(voter) = 40),
+    support          smallint not null check (support >= 0),
+    weight           numeric not null check (weight >= 0),
+    reason           text not null default '',
+    params           text not null default '',
+
+    primary key (dao_id, tx_hash, log_index)
+);
+
+create index {{prefix}}votes_proposal_idx on {{schema}}.{{prefix}}votes(dao_id, governor_address, proposal_id);
+create index {{prefix}}votes_voter_idx on {{schema}}.{{prefix}}votes(voter);
+
+comment on table {{schema}}.{{prefix}}votes is 'VoteCast and VoteCastWithParams events of the governors';
+comment on column {{schema}}.{{prefix}}votes.support is '0 against, 1 for, 2 abstain';
+
+create table if not exists {{schema}}.{{prefix}}proposal_state_changes
+(
+    dao_id           text not null,
+    governor_address text not null check (length(governor_address) = 40),
+    proposal_id      numeric not null,
+    tx_hash          text not null check (length(tx_hash) = 64),
+    log_index        integer not null check (log_index >= 0),
+    block_number     integer not null check (block_number > 0),
+    "timestamp"      integer not null check ("timestamp" > 1436940000),
+    state            text not null check (state in ('queued', 'executed', 'canceled')),
+    eta              bigint not null default 0,
+
+    primary key (dao_id, tx_hash, log_index)
+);
+
+create index {{prefix}}proposal_state_changes_proposal_idx on {{schema}}.{{prefix}}proposal_state_changes(dao_id, governor_address, proposal_id);
+create index {{prefix}}proposal_state_changes_tx_hash_idx on {{schema}}.{{prefix}}proposal_state_changes(tx_hash);
+
+comment on table {{schema}}.{{prefix}}proposal_state_changes is 'ProposalQueued, ProposalExecuted and ProposalCanceled events of the governors';
+comment on column {{schema}}.{{prefix}}proposal_state_changes.eta is 'timestamp after which a queued proposal may be executed.  0 for the other states';
+
+create or replace view {{schema}}.{{prefix}}proposal_results
+as
+    select
+    p.dao_id,
+    p.governor_address,
+    p.proposal_id,
+    p.proposer,
+    p.block_number,
+    p."timestamp",
+    p.description,
+    coalesce(s.state, 'created') as state,
+    coalesce(sum(v.weight) filter (where v.support = 0), 0) as votes_against,
+    coalesce(sum(v.weight) filter (where v.support = 1), 0) as votes_for,
+    coalesce(sum(v.weight) filter (where v.support = 2), 0) as votes_abstain,
+    count(v.voter) as voters
+    from {{schema}}.{{prefix}}proposals p
+    left join lateral (
+        select sc.state from {{schema}}.{{prefix}}proposal_state_changes sc
+        where sc.dao_id = p.dao_id and sc.governor_address = p.governor_address and sc.proposal_id = p.proposal_id
+        order by sc.block_number desc, sc.log_index desc limit 1
+    ) s on true
+    left join {{schema}}.{{prefix}}votes v on v.dao_id = p.dao_id and v.governor_address = p.governor_address and v.proposal_id = p.proposal_id
+    group by p.dao_id, p.governor_address, p.proposal_id, s.state;
+
+comment on view {{schema}}.{{prefix}}proposal_results is 'Last state and vote totals of each proposal.  Proposals pending, active, defeated or succeeded but not queued are in the created state';
+
+create or replace view {{schema}}.{{prefix}}proposal_transfers
+as
+    select
+    sc.governor_address,
+    sc.proposal_id,
+    t.*
+    from {{schema}}.{{prefix}}proposal_state_changes sc
+    join {{schema}}.{{prefix}}transfers t on t.dao_id = sc.dao_id and t.hash = sc.tx_hash
+    where sc.state = 'executed';
+
+comment on view {{schema}}.{{prefix}}proposal_transfers is 'Treasury transfers made in the transaction which executed a proposal, including its gas';
+
+
+-- Timelock operations:
+create table if not exists {{schema}}.{{prefix}}timelock_operations
+(
+    dao_id           text not null,
+    tx_hash          text not null check (length(tx_hash) = 64),
+    log_index        integer not null check (log_index >= 0),
+    timelock_address text not null check (length(timelock_address) = 40),
+    operation_id     text not null check (length(operation_id) = 64),
+    call_index       integer not null default 0 check (call_index >= 0),
+    block_number     integer not null check (block_number > 0),
+    "timestamp"      integer not null check ("timestamp" > 1436940000),
+    ordinal          integer not null check (ordinal >= 0),
+    state            text not null check (state in ('scheduled', 'executed', 'canceled')),
+    target           text not null default '' check (target = '' or length(target) = 40),
+    "value"          numeric check ("value" >= 0),
+    signature        text not null default '',
+    data             text not null default '',
+    predecessor      text not null default '',
+    eta              bigint not null default 0,
+    transfer_to      text not null default '' check (transfer_to = '' or length(transfer_to) = 40),
+    transfer_amount  numeric,
+
+    primary key (dao_id, tx_hash, log_index)
+);
+
+create index {{prefix}}timelock_operations_operation_idx on {{schema}}.{{prefix}}timelock_operations(dao_id, timelock_address, operation_id);
+
+comment on table {{schema}}.{{prefix}}timelock_operations is 'Calls scheduled, executed and canceled by the timelocks of frens.yaml';
+comment on column {{schema}}.{{prefix}}timelock_operations.operation_id is 'operation id of a TimelockController, or txHash of a Compound Timelock';
+comment on column {{schema}}.{{prefix}}timelock_operations.call_index is 'index of the call in a TimelockController batch.  0 for a Compound Timelock';
+comment on column {{schema}}.{{prefix}}timelock_operations.target is 'empty for a TimelockController Cancelled event, which cancels every call of the operation';
+comment on column {{schema}}.{{prefix}}timelock_operations.eta is 'timestamp after which the operation may be executed.  0 for TimelockController executions and cancellations';
+comment on column {{schema}}.{{prefix}}timelock_operations.transfer_to is 'recipient when the call is an ERC-20 transfer(address,uint256) of the target token';
+comment on column {{schema}}.{{prefix}}timelock_operations.transfer_amount is 'amount when the call is an ERC-20 transfer(address,uint256) of the target token, in its smallest unit';
+
+create or replace view {{schema}}.{{prefix}}pending_timelock_operations
+as
+    select s.*
+    from {{schema}}.{{prefix}}timelock_operations s
+    where s.state = 'scheduled'
+    and not exists (
+        select 1 from {{schema}}.{{prefix}}timelock_operations o
+        where o.dao_id = s.dao_id
+        and o.timelock_address = s.timelock_address
+        and o.operation_id = s.operation_id
+        and (o.block_number, o.ordinal) > (s.block_number, s.ordinal)
+        and ((o.state = 'canceled' and o.target = '') or (o.state <> 'scheduled' and o.call_index = s.call_index))
+    );
+
+comment on view {{schema}}.{{prefix}}pending_timelock_operations is 'Scheduled timelock calls which have not been executed or canceled, including the outflows they will make';
+
+create or replace view {{schema}}.{{prefix}}timelock_transfers
+as
+    select
+    o.timelock_address,
+    o.operation_id,
+    o.call_index,
+    t.*
+    from {{schema}}.{{prefix}}timelock_operations o
+    join {{schema}}.{{prefix}}transfers t on t.dao_id = o.dao_id and t.hash = o.tx_hash and t.from_address = o.timelock_address
+    where o.state = 'executed'
+    and (
+        (t.token_address = 'ETH' and t.to_address = o.target and t."value" = o."value" and o."value" > 0)
+        or (t.token_address = o.target and t.to_address = o.transfer_to and t."value" = o.transfer_amount)
+    );
+
+comment on view {{schema}}.{{prefix}}timelock_transfers is 'Treasury transfers made by executed timelock calls: the ETH value of the call, or the ERC-20 transfer it makes';
+
+
+-- ERC-4626 vaults:
+create table if not exists {{schema}}.{{prefix}}vault_events
+(
+    dao_id        text not null,
+    tx_hash       text not null check (length(tx_hash) = 64),
+    log_index     integer not null check (log_index >= 0),
+    vault_address text not null check (length(vault_address) = 40),
+    asset_address text not null default '' check (asset_address = '' or length(asset_address) = 40),
+    block_number  integer not null check (block_number > 0),
+    "timestamp"   integer not null check ("timestamp" > 1436940000),
+    ordinal       integer not null check (ordinal >= 0),
+    kind          text not null check (kind in ('deposit', 'withdraw')),
+    sender        text not null check (length(sender) = 40),
+    receiver      text not null check (length(receiver) = 40),
+    owner         text not null check (length(owner) = 40),
+    assets        numeric not null check (assets >= 0),
+    shares        numeric not null check (shares >= 0),
+
+    primary key (dao_id, tx_hash, log_index)
+);
+
+create index {{prefix}}vault_events_vault_idx on {{schema}}.{{prefix}}vault_events(dao_id, vault_address, owner);
+
+comment on table {{schema}}.{{prefix}}vault_events is 'ERC-4626 Deposit and Withdraw events whose owner or receiver is a DAO account';
+comment on column {{schema}}.{{prefix}}vault_events.asset_address is 'token transferred into the vault by the deposit, or out of it by the withdrawal.  Empty when no such transfer was found';
+comment on column {{schema}}.{{prefix}}vault_events.assets is 'assets deposited or withdrawn, in the smallest unit of the asset';
+comment on column {{schema}}.{{prefix}}vault_events.shares is 'shares minted or burned, in the smallest unit of the vault';
+
+create table if not exists {{schema}}.{{prefix}}vault_prices
+(
+    vault_address text not null check (length(vault_address) = 40),
+    block_number  integer not null check (block_number > 0),
+    "timestamp"   integer not null check ("timestamp" > 1436940000),
+    assets        numeric not null check (assets >= 0),
+    shares        numeric not null check (shares > 0),
+
+    primary key (vault_address, block_number)
+);
+
+comment on table {{schema}}.{{prefix}}vault_prices is 'Assets and shares of the last Deposit or Withdraw of each vault used by the DAO accounts, at every block with one';
+
+create or replace view {{schema}}.{{prefix}}vault_positions
+as
+    select
+    b.dao_id,
+    b.address,
+    b.token_address as vault_address,
+    coalesce(e.asset_address, '') as asset_address,
+    b.balance as shares,
+    b.balance * p.assets / p.shares as assets,
+    coalesce(e.deposited, 0) - coalesce(e.withdrawn, 0) as net_deposited,
+    b.balance * p.assets / p.shares / power(10, t.decimals) * {{schema}}.{{prefix}}token_price(e.asset_address, p.block_number) as value_usd,
+    p.block_number as price_block_number
+    from (
+        select distinct on (dao_id, address, token_address) dao_id, address, token_address, balance
+        from {{schema}}.{{prefix}}account_balances
+        where token_id = ''
+        and token_address in (select vault_address from {{schema}}.{{prefix}}vault_events)
+        order by dao_id, address, token_address, block_number desc, ordinal desc
+    ) b
+    join lateral (
+        select vp.assets, vp.shares, vp.block_number from {{schema}}.{{prefix}}vault_prices vp
+        where vp.vault_address = b.token_address
+        order by vp.block_number desc limit 1
+    ) p on true
+    left join lateral (
+        select
+        max(nullif(ve.asset_address, '')) as asset_address,
+        sum(case when ve.kind = 'deposit' and ve.owner = b.address then ve.assets else 0 end) as deposited,
+        sum(case when ve.kind = 'withdraw' and ve.owner = b.address then ve.assets else 0 end) as withdrawn
+        from {{schema}}.{{prefix}}vault_events ve
+        where ve.dao_id = b.dao_id and ve.vault_address = b.token_address
+    ) e on true
+    left join {{schema}}.{{prefix}}tokens t on t.token_address = e.asset_address
+    where b.balance > 0;
+
+comment on view {{schema}}.{{prefix}}vault_positions is 'Vault shares held by the DAO accounts, with the assets they are worth at the last price of the vault';
+comment on column {{schema}}.{{prefix}}vault_positions.net_deposited is 'assets deposited less assets withdrawn by the account.  assets - net_deposited is the yield, unless shares were transferred';
+comment on column {{schema}}.{{prefix}}vault_positions.value_usd is 'null when the asset has no price feed or decimals';
+
+-- DEX trades:
+create table if not exists {{schema}}.{{prefix}}trades
+(
+    dao_id        text not null,
+    tx_hash       text not null check (length(tx_hash) = 64),
+    log_index     integer not null check (log_index >= 0),
+    account       text not null check (length(account) = 40),
+    block_number  integer not null check (block_number > 0),
+    "timestamp"   integer not null check ("timestamp" > 1436940000),
+    ordinal       integer not null check (ordinal >= 0),
+    protocol      text not null check (protocol in ('uniswap_v2', 'uniswap_v3', 'curve', 'balancer_v2')),
+    pool_address  text not null check (length(pool_address) = 40),
+    sender        text not null check (length(sender) = 40),
+    recipient     text not null default '' check (recipient = '' or length(recipient) = 40),
+    sold_token    text not null check (length(sold_token) = 40),
+    sold_amount   numeric not null check (sold_amount >= 0),
+    bought_token  text not null check (length(bought_token) = 40),
+    bought_amount numeric not null check (bought_amount >= 0),
+    price         numeric,
+    value_usd     numeric,
+
+    primary key (dao_id, tx_hash, log_index)
+);
+
+create index {{prefix}}trades_account_idx on {{schema}}.{{prefix}}trades(dao_id, account, block_number);
+
+comment on table {{schema}}.{{prefix}}trades is 'Uniswap V2 and V3, Curve and Balancer V2 swaps made by or for the DAO accounts';
+comment on column {{schema}}.{{prefix}}trades.account is 'DAO account behind the swap: its recipient, its sender, the caller of its sender or the transaction sender';
+comment on column {{schema}}.{{prefix}}trades.pool_address is 'address of the pool.  For Balancer, the first 20 bytes of the pool id';
+comment on column {{schema}}.{{prefix}}trades.recipient is 'receiver of the bought tokens.  Empty for Balancer';
+comment on column {{schema}}.{{prefix}}trades.price is 'bought tokens per sold token, adjusted for their decimals.  Null when the substream does not know the decimals';
+comment on column {{schema}}.{{prefix}}trades.value_usd is 'USD value of the sold tokens, or of the bought tokens when the sold ones have no price';
+
+create or replace view {{schema}}.{{prefix}}trade_history
+as
+    select
+    tr.dao_id,
+    tr.block_number,
+    tr."timestamp",
+    tr.tx_hash,
+    tr.account,
+    tr.protocol,
+    tr.pool_address,
+    tr.sold_token,
+    coalesce(nullif(s.symbol, ''), tr.sold_token) as sold_symbol,
+    tr.sold_amount / power(10, s.decimals) as sold,
+    tr.bought_token,
+    coalesce(nullif(b.symbol, ''), tr.bought_token) as bought_symbol,
+    tr.bought_amount / power(10, b.decimals) as bought,
+    coalesce(tr.price, (tr.bought_amount / power(10, b.decimals)) / nullif(tr.sold_amount / power(10, s.decimals), 0)) as price,
+    tr.value_usd
+    from {{schema}}.{{prefix}}trades tr
+    left join {{schema}}.{{prefix}}tokens s on s.token_address = tr.sold_token
+    left join {{schema}}.{{prefix}}tokens b on b.token_address = tr.bought_token;
+
+comment on view {{schema}}.{{prefix}}trade_history is 'Trades with the symbols and decimal amounts of the tokens, and the price from the token metadata when the substream has none';
+
+
+-- Lending positions:
+create table if not exists {{schema}}.{{prefix}}lending_events
+(
+    dao_id          text not null,
+    tx_hash         text not null check (length(tx_hash) = 64),
+    log_index       integer not null check (log_index >= 0),
+    side            text not null check (side in ('collateral', 'debt')),
+    account         text not null check (length(account) = 40),
+    block_number    integer not null check (block_number > 0),
+    "timestamp"     integer not null check ("timestamp" > 1436940000),
+    ordinal         integer not null check (ordinal >= 0),
+    protocol        text not null check (protocol in ('aave_v2', 'aave_v3', 'compound')),
+    market_address  text not null check (length(market_address) = 40),
+    asset_address   text not null default '' check (asset_address = '' or asset_address = 'ETH' or length(asset_address) = 40),
+    kind            text not null check (kind in ('supply', 'withdraw', 'borrow', 'repay', 'liquidation')),
+    amount          numeric check (amount >= 0),
+    tokens          numeric check (tokens >= 0),
+    account_borrows numeric check (account_borrows >= 0),
+    counterparty    text not null default '' check (counterparty = '' or length(counterparty) = 40),
+
+    primary key (dao_id, tx_hash, log_index, side)
+);
+
+create index {{prefix}}lending_events_account_idx on {{schema}}.{{prefix}}lending_events(dao_id, account, market_address);
+
+comment on table {{schema}}.{{prefix}}lending_events is 'Supplies, withdrawals, borrows, repayments and liquidations of the DAO accounts on Aave V2 and V3 and Compound';
+comment on column {{schema}}.{{prefix}}lending_events.side is 'collateral or debt.  Aave liquidations, and Aave V3 repayments with aTokens, change both sides in one event';
+comment on column {{schema}}.{{prefix}}lending_events.market_address is 'Aave pool or Compound cToken';
+comment on column {{schema}}.{{prefix}}lending_events.asset_address is 'token supplied or borrowed.  Empty for Compound liquidations, which seize cTokens';
+comment on column {{schema}}.{{prefix}}lending_events.amount is 'assets moved.  Null for Compound liquidations';
+comment on column {{schema}}.{{prefix}}lending_events.tokens is 'cTokens minted, redeemed or seized.  Null for Aave';
+comment on column {{schema}}.{{prefix}}lending_events.account_borrows is 'debt of the account after a Compound borrow or repay, interest included';
+
+create or replace view {{schema}}.{{prefix}}lending_positions
+as
+    with positions as (
+        -- Aave: principal supplied and borrowed, without the interest accrued since
+        select
+        le.dao_id,
+        le.account,
+        le.protocol,
+        le.market_address,
+        le.asset_address,
+        greatest(sum(case when le.side = 'collateral' then (case when le.kind = 'supply' then le.amount else -le.amount end) else 0 end), 0) as collateral,
+        greatest(sum(case when le.side = 'debt' then (case when le.kind = 'borrow' then le.amount else -le.amount end) else 0 end), 0) as debt,
+        max(le.block_number) as block_number
+        from {{schema}}.{{prefix}}lending_events le
+        where le.protocol in ('aave_v2', 'aave_v3')
+        group by le.dao_id, le.account, le.protocol, le.market_address, le.asset_address
+
+        union all
+
+        -- Compound: cTokens held at the last exchange rate of the market, and the debt of the last borrow or repay
+        select
+        m.dao_id,
+        m.account,
+        'compound' as protocol,
+        m.market_address,
+        m.asset_address,
+        coalesce(b.balance * r.amount / r.tokens, 0) as collateral,
+        coalesce(d.account_borrows, 0) as debt,
+        m.block_number
+        from (
+            select le.dao_id, le.account, le.market_address, max(nullif(le.asset_address, '')) as asset_address, max(le.block_number) as block_number
+            from {{schema}}.{{prefix}}lending_events le
+            where le.protocol = 'compound'
+            group by le.dao_id, le.account, le.market_address
+        ) m
+        left join lateral (
+            select ab.balance from {{schema}}.{{prefix}}account_balances ab
+            where ab.dao_id = m.dao_id and ab.address = m.account and ab.token_address = m.market_address and ab.token_id = ''
+            order by ab.block_number desc, ab.ordinal desc limit 1
+        ) b on true
+        left join lateral (
+            select re.amount, re.tokens from {{schema}}.{{prefix}}lending_events re
+            where re.market_address = m.market_address and re.kind in ('supply', 'withdraw') and re.tokens > 0
+            order by re.block_number desc, re.ordinal desc limit 1
+        ) r on true
+        left join lateral (
+            select de.account_borrows from {{schema}}.{{prefix}}lending_events de
+            where de.dao_id = m.dao_id and de.account = m.account and de.market_address = m.market_address and de.account_borrows is not null
+            order by de.block_number desc, de.ordinal desc limit 1
+        ) d on true
+    )
+    select
+    p.dao_id,
+    p.account,
+    p.protocol,
+    p.market_address,
+    p.asset_address,
+    p.collateral,
+    p.debt,
+    p.collateral / power(10, t.decimals) * {{schema}}.{{prefix}}token_price(p.asset_address, 2147483647) as collateral_usd,
+    p.debt / power(10, t.decimals) * {{schema}}.{{prefix}}token_price(p.asset_address, 2147483647) as debt_usd,
+    p.block_number
+    from positions p
+    left join {{schema}}.{{prefix}}tokens t on t.token_address = p.asset_address
+    where p.collateral > 0 or p.debt > 0;
+
+comment on view {{schema}}.{{prefix}}lending_positions is 'Collateral and debt of the DAO accounts for each lending market and asset';
+comment on column {{schema}}.{{prefix}}lending_positions.collateral is 'Aave: principal supplied less withdrawn, repaid with aTokens and liquidated.  Compound: cTokens held at the last exchange rate seen';
+comment on column {{schema}}.{{prefix}}lending_positions.debt is 'Aave: principal borrowed less repaid and liquidated.  Compound: debt at the last borrow or repay, interest included';
+comment on column {{schema}}.{{prefix}}lending_positions.collateral_usd is 'at the last price of the asset.  Null when the asset has no price feed or decimals';
+comment on column {{schema}}.{{prefix}}lending_positions.block_number is 'block of the last event of the position';
+
+
+-- Uniswap V3 LP positions:
+create table if not exists {{schema}}.{{prefix}}lp_events
+(
+    dao_id        text not null,
+    tx_hash       text not null check (length(tx_hash) = 64),
+    log_index     integer not null check (log_index >= 0),
+    owner         text not null check (length(owner) = 40),
+    token_id      text not null,
+    block_number  integer not null check (block_number > 0),
+    "timestamp"   integer not null check ("timestamp" > 1436940000),
+    ordinal       integer not null check (ordinal >= 0),
+    kind          text not null check (kind in ('increase', 'decrease', 'collect')),
+    pool_address  text not null check (length(pool_address) = 40),
+    tick_lower    integer not null,
+    tick_upper    integer not null,
+    token0        text not null default '' check (token0 = '' or length(token0) = 40),
+    token1        text not null default '' check (token1 = '' or length(token1) = 40),
+    liquidity     numeric check (liquidity >= 0),
+    amount0       numeric not null check (amount0 >= 0),
+    amount1       numeric not null check (amount1 >= 0),
+    recipient     text not null default '' check (recipient = '' or length(recipient) = 40),
+
+    primary key (dao_id, tx_hash, log_index)
+);
+
+create index {{prefix}}lp_events_token_id_idx on {{schema}}.{{prefix}}lp_events(dao_id, token_id, block_number);
+
+comment on table {{schema}}.{{prefix}}lp_events is 'Liquidity added and removed, and tokens collected, by the Uniswap V3 LP NFTs of the DAO accounts';
+comment on column {{schema}}.{{prefix}}lp_events.token_id is 'token id of the NonfungiblePositionManager NFT, as in token_transfers';
+comment on column {{schema}}.{{prefix}}lp_events.token0 is 'empty when no token0 was transferred with the event.  lp_positions takes it from the other events of the pool';
+comment on column {{schema}}.{{prefix}}lp_events.liquidity is 'liquidity added or removed.  Null for collect';
+comment on column {{schema}}.{{prefix}}lp_events.amount0 is 'token0 added, removed or collected.  Tokens removed stay in the pool until collected, with the fees earned';
+
+create or replace view {{schema}}.{{prefix}}lp_positions
+as
+    select
+    p.dao_id,
+    p.token_id,
+    p.owner,
+    p.pool_address,
+    p.tick_lower,
+    p.tick_upper,
+    t.token0,
+    t.token1,
+    p.liquidity,
+    p.deposited0,
+    p.deposited1,
+    p.withdrawn0,
+    p.withdrawn1,
+    p.collected0,
+    p.collected1,
+    greatest(p.collected0 - p.withdrawn0, 0) as fees0,
+    greatest(p.collected1 - p.withdrawn1, 0) as fees1,
+    p.block_number
+    from (
+        select
+        e.dao_id,
+        e.token_id,
+        (array_agg(e.owner order by e.block_number desc, e.ordinal desc))[1] as owner,
+        max(e.pool_address) as pool_address,
+        max(e.tick_lower) as tick_lower,
+        max(e.tick_upper) as tick_upper,
+        sum(case e.kind when 'increase' then e.liquidity when 'decrease' then -e.liquidity else 0 end) as liquidity,
+        sum(case when e.kind = 'increase' then e.amount0 else 0 end) as deposited0,
+        sum(case when e.kind = 'increase' then e.amount1 else 0 end) as deposited1,
+        sum(case when e.kind = 'decrease' then e.amount0 else 0 end) as withdrawn0,
+        sum(case when e.kind = 'decrease' then e.amount1 else 0 end) as withdrawn1,
+        sum(case when e.kind = 'collect' then e.amount0 else 0 end) as collected0,
+        sum(case when e.kind = 'collect' then e.amount1 else 0 end) as collected1,
+        max(e.block_number) as block_number
+        from {{schema}}.{{prefix}}lp_events e
+        group by e.dao_id, e.token_id
+    ) p
+    left join lateral (
+        select max(nullif(pe.token0, '')) as token0, max(nullif(pe.token1, '')) as token1
+        from {{schema}}.{{prefix}}lp_events pe
+        where pe.pool_address = p.pool_address
+    ) t on true;
+
+comment on view {{schema}}.{{prefix}}lp_positions is 'Liquidity, tokens deposited and withdrawn, and fees collected of the Uniswap V3 LP positions of the DAO accounts';
+comment on column {{schema}}.{{prefix}}lp_positions.owner is 'owner at the last event of the position.  Transfers of the NFT are in token_transfers';
+comment on column {{schema}}.{{prefix}}lp_positions.fees0 is 'token0 collected beyond the amounts withdrawn.  Fees not yet collected are not included';
+
+
+-- Token streams:
+create table if not exists {{schema}}.{{prefix}}stream_events
+(
+    dao_id           text not null,
+    tx_hash          text not null check (length(tx_hash) = 64),
+    log_index        integer not null check (log_index >= 0),
+    block_number     integer not null check (block_number > 0),
+    "timestamp"      integer not null check ("timestamp" > 1436940000),
+    ordinal          integer not null check (ordinal >= 0),
+    protocol         text not null check (protocol in ('sablier_v1', 'sablier_v2', 'llamapay')),
+    contract_address text not null check (length(contract_address) = 40),
+    stream_id        text not null,
+    kind             text not null check (kind in ('create', 'withdraw', 'cancel', 'pause')),
+    sender           text not null default '' check (sender = '' or length(sender) = 40),
+    recipient        text not null default '' check (recipient = '' or length(recipient) = 40),
+    token_address    text not null default '' check (token_address = '' or length(token_address) = 40),
+    deposit          numeric check (deposit >= 0),
+    amount_per_sec   numeric check (amount_per_sec >= 0),
+    start_time       bigint not null default 0,
+    cliff_time       bigint not null default 0,
+    stop_time        bigint not null default 0,
+    amount           numeric check (amount >= 0),
+    refund           numeric check (refund >= 0),
+
+    primary key (dao_id, tx_hash, log_index)
+);
+
+create index {{prefix}}stream_events_stream_idx on {{schema}}.{{prefix}}stream_events(dao_id, contract_address, stream_id);
+
+comment on table {{schema}}.{{prefix}}stream_events is 'Creations, withdrawals, cancellations and pauses of the Sablier V1, Sablier V2 LockupLinear and LlamaPay streams sent or received by the DAO accounts';
+comment on column {{schema}}.{{prefix}}stream_events.stream_id is 'Sablier stream id, or LlamaPay stream id in hex';
+comment on column {{schema}}.{{prefix}}stream_events.token_address is 'empty for LlamaPay streams until their first withdrawal, and for withdrawals whose transfer was not found';
+comment on column {{schema}}.{{prefix}}stream_events.amount_per_sec is 'tokens streamed per second.  In the smallest unit of the token for Sablier, and with 20 decimals for LlamaPay';
+comment on column {{schema}}.{{prefix}}stream_events.start_time is 'unix timestamp.  LlamaPay streams start when created, and stop_time is set by their cancel or pause';
+comment on column {{schema}}.{{prefix}}stream_events.amount is 'amount withdrawn, or due to the recipient on cancel, in the smallest unit of the token';
+
+create or replace view {{schema}}.{{prefix}}streams
+as
+    with clock as (
+        select extract(epoch from now())::bigint as now
+    ),
+    sablier as (
+        select
+        c.dao_id,
+        c.protocol,
+        c.contract_address,
+        c.stream_id,
+        c.sender,
+        c.recipient,
+        c.token_address,
+        c.deposit,
+        c.amount_per_sec,
+        c.start_time,
+        c.cliff_time,
+        c.stop_time,
+        case
+            when x.tx_hash is not null then 'cancelled'
+            when clock.now >= c.stop_time then 'ended'
+            else 'active'
+        end as status,
+        case
+            when x.tx_hash is not null then w.withdrawn_before_cancel + x.amount
+            when clock.now < c.cliff_time then 0
+            else c.deposit * least(greatest(clock.now - c.start_time, 0)::numeric / nullif(c.stop_time - c.start_time, 0), 1)
+        end as streamed,
+        -- Sablier V1 pays the recipient on cancel, V2 leaves the amount to withdraw
+        w.withdrawn + case when c.protocol = 'sablier_v1' then coalesce(x.amount, 0) else 0 end as paid
+        from {{schema}}.{{prefix}}stream_events c
+        cross join clock
+        left join {{schema}}.{{prefix}}stream_events x
+            on x.dao_id = c.dao_id and x.contract_address = c.contract_address and x.stream_id = c.stream_id and x.kind = 'cancel'
+        join lateral (
+            select
+            coalesce(sum(we.amount), 0) as withdrawn,
+            coalesce(sum(case when x.tx_hash is null or (we.block_number, we.ordinal) < (x.block_number, x.ordinal) then we.amount else 0 end), 0) as withdrawn_before_cancel
+            from {{schema}}.{{prefix}}stream_events we
+            where we.dao_id = c.dao_id and we.contract_address = c.contract_address and we.stream_id = c.stream_id and we.kind = 'withdraw'
+        ) w on true
+        where c.protocol in ('sablier_v1', 'sablier_v2') and c.kind = 'create'
+    ),
+    llamapay_intervals as (
+        select
+        e.*,
+        lead(e.stop_time) over (partition by e.dao_id, e.contract_address, e.stream_id order by e.block_number, e.ordinal) as next_stop_time
+        from {{schema}}.{{prefix}}stream_events e
+        where e.protocol = 'llamapay' and e.kind in ('create', 'cancel', 'pause')
+    ),
+    llamapay as (
+        select
+        i.dao_id,
+        'llamapay' as protocol,
+        i.contract_address,
+        i.stream_id,
+        max(i.sender) as sender,
+        max(i.recipient) as recipient,
+        max(i.amount_per_sec) as amount_per_sec,
+        min(i.start_time) filter (where i.kind = 'create') as start_time,
+        (array_agg(i.kind order by i.block_number desc, i.ordinal desc))[1] as last_kind,
+        sum(case when i.kind = 'create' then i.amount_per_sec * (coalesce(i.next_stop_time, clock.now) - i.start_time) else 0 end) as streamed
+        from llamapay_intervals i
+        cross join clock
+        group by i.dao_id, i.contract_address, i.stream_id
+    ),
+    positions as (
+        select
+        dao_id, protocol, contract_address, stream_id, sender, recipient, token_address, deposit, amount_per_sec,
+        start_time, cliff_time, stop_time, status, streamed, paid
+        from sablier
+
+        union all
+
+        -- LlamaPay rates have 20 decimals, converted to the smallest unit of the token which the withdrawals are in
+        select
+        l.dao_id,
+        l.protocol,
+        l.contract_address,
+        l.stream_id,
+        l.sender,
+        l.recipient,
+        coalesce(tk.token_address, '') as token_address,
+        null as deposit,
+        l.amount_per_sec / power(10, 20 - t.decimals) as amount_per_sec,
+        l.start_time,
+        0 as cliff_time,
+        0 as stop_time,
+        case l.last_kind when 'cancel' then 'cancelled' when 'pause' then 'paused' else 'active' end as status,
+        l.streamed / power(10, 20 - t.decimals) as streamed,
+        coalesce(w.withdrawn, 0) as paid
+        from llamapay l
+        left join lateral (
+            select max(nullif(te.token_address, '')) as token_address
+            from {{schema}}.{{prefix}}stream_events te
+            where te.contract_address = l.contract_address
+        ) tk on true
+        left join lateral (
+            select sum(we.amount) as withdrawn
+            from {{schema}}.{{prefix}}stream_events we
+            where we.dao_id = l.dao_id and we.contract_address = l.contract_address and we.stream_id = l.stream_id and we.kind = 'withdraw'
+        ) w on true
+        left join {{schema}}.{{prefix}}tokens t on t.token_address = tk.token_address
+    )
+    select
+    p.*,
+    case when a.address is not null then 'outgoing' else 'incoming' end as direction,
+    p.streamed - p.paid as unpaid,
+    case when p.status in ('active', 'ended') then p.deposit - p.streamed end as remaining
+    from positions p
+    left join {{schema}}.{{prefix}}accounts a on a.dao_id = p.dao_id and a.address = p.sender;
+
+comment on view {{schema}}.{{prefix}}streams is 'Streams sent or received by the DAO accounts, with the amounts streamed and paid so far.  Only streams whose creation was indexed';
+comment on column {{schema}}.{{prefix}}streams.amount_per_sec is 'in the smallest unit of the token.  Null for LlamaPay streams whose token decimals are unknown';
+comment on column {{schema}}.{{prefix}}streams.streamed is 'amount vested to the recipient so far, in the smallest unit of the token';
+comment on column {{schema}}.{{prefix}}streams.paid is 'amount transferred to the recipient so far';
+comment on column {{schema}}.{{prefix}}streams.unpaid is 'amount streamed but not yet withdrawn by the recipient';
+comment on column {{schema}}.{{prefix}}streams.remaining is 'deposit still to be streamed by a Sablier stream.  Null for LlamaPay, whose streams have no end';
+
+create or replace view {{schema}}.{{prefix}}stream_liabilities
+as
+    select
+    s.dao_id,
+    s.token_address,
+    count(*) as streams,
+    sum(s.unpaid) as unpaid,
+    sum(s.remaining) as remaining,
+    sum(s.unpaid) / power(10, t.decimals) * {{schema}}.{{prefix}}token_price(s.token_address, 2147483647) as unpaid_usd,
+    sum(s.remaining) / power(10, t.decimals) * {{schema}}.{{prefix}}token_price(s.token_address, 2147483647) as remaining_usd
+    from {{schema}}.{{prefix}}streams s
+    left join {{schema}}.{{prefix}}tokens t on t.token_address = s.token_address
+    where s.direction = 'outgoing'
+    group by s.dao_id, s.token_address, t.decimals;
+
+comment on view {{schema}}.{{prefix}}stream_liabilities is 'Committed but unpaid amounts of the streams sent by the DAO accounts, for each token';
+comment on column {{schema}}.{{prefix}}stream_liabilities.unpaid is 'streamed to the recipients but not yet withdrawn';
+comment on column {{schema}}.{{prefix}}stream_liabilities.remaining is 'still to be streamed by the Sablier streams';
+
+
+-- Airdrops:
+create table if not exists {{schema}}.{{prefix}}distributors
+(
+    dao_id        text not null,
+    address       text not null check (length(address) = 40),
+    name          text not null,
+    token_address text not null check (length(token_address) = 40),
+    total         numeric check (total >= 0),
+
+    primary key(dao_id, address)
+);
+
+comment on table {{schema}}.{{prefix}}distributors is 'MerkleDistributor contracts of airdrops from the frens.yaml distributors';
+comment on column {{schema}}.{{prefix}}distributors.total is 'total of the airdrop in the smallest unit of the token.  Null when not set in frens.yaml';
+
+create table if not exists {{schema}}.{{prefix}}claims
+(
+    dao_id              text not null,
+    distributor_address text not null check (length(distributor_address) = 40),
+    claim_index         numeric not null check (claim_index >= 0),
+    block_number        integer not null check (block_number > 0),
+    "timestamp"         integer not null check ("timestamp" > 1436940000),
+    tx_hash             text not null check (length(tx_hash) = 64),
+    log_index           integer not null check (log_index >= 0),
+    ordinal             integer not null check (ordinal >= 0),
+    token_address       text not null check (length(token_address) = 40),
+    account             text not null check (length(account) = 40),
+    amount              numeric not null check (amount >= 0),
+
+    primary key (dao_id, distributor_address, claim_index)
+);
+
+create index {{prefix}}claims_account_idx on {{schema}}.{{prefix}}claims(account);
+
+comment on table {{schema}}.{{prefix}}claims is 'Claimed events of the airdrop distributors';
+comment on column {{schema}}.{{prefix}}claims.claim_index is 'index of the claim in the merkle tree';
+
+create or replace view {{schema}}.{{prefix}}distribution_totals
+as
+    select
+    d.dao_id,
+    d.address as distributor_address,
+    d.name,
+    d.token_address,
+    d.total,
+    count(c.claim_index) as claims,
+    coalesce(sum(c.amount), 0) as claimed,
+    d.total - coalesce(sum(c.amount), 0) as unclaimed,
+    coalesce(sum(c.amount), 0) / power(10, t.decimals) * {{schema}}.{{prefix}}token_price(d.token_address, 2147483647) as claimed_usd,
+    (d.total - coalesce(sum(c.amount), 0)) / power(10, t.decimals) * {{schema}}.{{prefix}}token_price(d.token_address, 2147483647) as unclaimed_usd,
+    max(c."timestamp") as last_claim_timestamp
+    from {{schema}}.{{prefix}}distributors d
+    left join {{schema}}.{{prefix}}claims c on c.dao_id = d.dao_id and c.distributor_address = d.address
+    left join {{schema}}.{{prefix}}tokens t on t.token_address = d.token_address
+    group by d.dao_id, d.address, d.name, d.token_address, d.total, t.decimals;
+
+comment on view {{schema}}.{{prefix}}distribution_totals is 'Claimed and unclaimed amounts of each airdrop, in the smallest unit of the token';
+comment on column {{schema}}.{{prefix}}distribution_totals.unclaimed is 'total less the amount claimed.  Null when the total is not set in frens.yaml';
+
+
+-- Utlity functions:
+create or replace function {{schema}}.{{prefix}}is_burn_address(text)
+returns boolean
+as $$
+begin
+    if (
+	   $1 = '0000000000000000000000000000000000000000'
+	or $1 = '000000000000000000000000000000000000dead'
+    )
+        then return true;
+    else
+        return false;
+    end if;
+end;
+$$
+language plpgsql;
+
+-- substreams-sink-postgres cursors:
+create table if not exists {{schema}}.cursors
+(
+    id         text not null constraint cursor_pk primary key,
+    cursor     text,
+    block_num  bigint,
+    block_id   text
+);
+
+commit;
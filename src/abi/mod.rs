@@ -1,4 +1,26 @@
+pub mod aave_v2_atoken;
+pub mod aave_v2_pool;
+pub mod aave_v3_atoken;
+pub mod aave_v3_pool;
+pub mod balancer_vault;
+pub mod chainlink_aggregator;
+pub mod compound_ctoken;
+pub mod compound_timelock;
+pub mod curve_pool;
 pub mod erc1155;
 pub mod erc20;
+pub mod erc20_votes;
+pub mod erc4626;
 pub mod erc721;
+pub mod governor;
+pub mod lido;
+pub mod llamapay;
+pub mod merkle_distributor;
+pub mod sablier_v1;
+pub mod sablier_v2_lockup_linear;
+pub mod safe_proxy_factory;
+pub mod timelock_controller;
+pub mod uniswap_v2_pair;
+pub mod uniswap_v3_pool;
+pub mod uniswap_v3_position_manager;
 pub mod weth;
@@ -1,5 +1,11 @@
-use crate::pb::frenscan::{CallTraceRecord, TokenTransfer, Transfers, ValueTransfer};
-use std::collections::HashMap;
+use crate::pb::frenscan::{
+    CallTraceRecord, Claims, DelegateVotesChange, Delegations, DiscoveredAccounts, FeedPrice,
+    FeedPrices, GovernanceEvents, LendingEvents, LpEvents, NetWorths, PeriodSnapshots,
+    StreamEvents, TimelockOperations, TokenTransfer, Trades, Transfers, ValueTransfer, VaultEvents,
+    VaultPrice, VaultPrices,
+};
+use crate::settings::TABLE_PREFIX;
+use std::collections::{BTreeMap, HashMap};
 use substreams_database_change::pb::database::{table_change::Operation, DatabaseChanges};
 
 pub fn transfers_to_database_changes(changes: &mut DatabaseChanges, transfers: Transfers) {
@@ -24,6 +30,43 @@ pub fn transfers_to_database_changes(changes: &mut DatabaseChanges, transfers: T
     push_create_call_traces(changes, transfers.call_traces);
 }
 
+pub fn discovered_accounts_to_database_changes(
+    changes: &mut DatabaseChanges,
+    discovered: DiscoveredAccounts,
+) {
+    for account in discovered.accounts.iter() {
+        let name = if account.factory.is_empty() {
+            format!("Created by {}", account.creator)
+        } else {
+            account.factory.to_string()
+        };
+
+        let pk: HashMap<String, String> = HashMap::from([
+            (String::from("dao_id"), account.dao_id.to_string()),
+            (String::from("address"), account.address.to_string()),
+        ]);
+
+        changes.push_change_composite(
+            table_name("accounts"),
+            pk.clone(),
+            account.block_number,
+            Operation::Create,
+        );
+
+        changes
+            .push_change_composite(
+                table_name("accounts_metadata"),
+                pk,
+                account.block_number,
+                Operation::Create,
+            )
+            .change("name", (None, &name))
+            .change("tags", (None, &"{discovered}".to_string()))
+            .change("discovered_block", (None, account.block_number))
+            .change("discovered_tx_hash", (None, &account.tx_hash));
+    }
+}
+
 fn push_create_token_transfers(
     changes: &mut DatabaseChanges,
     transfers: Vec<TokenTransfer>,
@@ -32,20 +75,31 @@ fn push_create_token_transfers(
 ) {
     for transfer in transfers.iter() {
         let pk: HashMap<String, String> = HashMap::from([
+            (String::from("dao_id"), transfer.dao_id.to_string()),
             (String::from("tx_hash"), transfer.tx_hash.to_string()),
             (String::from("log_index"), transfer.log_index.to_string()),
+            (String::from("reason"), transfer.reason.to_string()),
+            (String::from("from_address"), transfer.from.to_string()),
+            (String::from("to_address"), transfer.to.to_string()),
         ]);
 
-        changes
-            .push_change_composite("token_transfers", pk, transfer.log_index, Operation::Create)
+        let change = changes
+            .push_change_composite(
+                table_name("token_transfers"),
+                pk,
+                transfer.ordinal,
+                Operation::Create,
+            )
+            .change("ordinal", (None, transfer.ordinal))
             .change("call_index", (None, transfer.call_index))
-            .change("from_address", (None, &transfer.from.to_string()))
-            .change("to_address", (None, &transfer.to.to_string()))
             .change("block_number", (None, block_number))
             .change("value", (None, &transfer.value))
             .change("token_address", (None, &transfer.token_address.to_string()))
             .change("token_id", (None, &transfer.token_id))
             .change("timestamp", (None, block_timestamp));
+        if !transfer.value_usd.is_empty() {
+            change.change("value_usd", (None, &transfer.value_usd));
+        }
     }
 }
 
@@ -57,17 +111,19 @@ fn push_create_issued_token_transfers(
 ) {
     for transfer in transfers.iter() {
         let pk: HashMap<String, String> = HashMap::from([
+            (String::from("dao_id"), transfer.dao_id.to_string()),
             (String::from("tx_hash"), transfer.tx_hash.to_string()),
             (String::from("log_index"), transfer.log_index.to_string()),
         ]);
 
-        changes
+        let change = changes
             .push_change_composite(
-                "tokens_issued_transfers",
+                table_name("tokens_issued_transfers"),
                 pk,
-                transfer.log_index,
+                transfer.ordinal,
                 Operation::Create,
             )
+            .change("ordinal", (None, transfer.ordinal))
             .change("call_index", (None, transfer.call_index))
             .change("from_address", (None, &transfer.from.to_string()))
             .change("to_address", (None, &transfer.to.to_string()))
@@ -76,6 +132,9 @@ fn push_create_issued_token_transfers(
             .change("token_address", (None, &transfer.token_address.to_string()))
             .change("token_id", (None, &transfer.token_id))
             .change("timestamp", (None, block_timestamp));
+        if !transfer.value_usd.is_empty() {
+            change.change("value_usd", (None, &transfer.value_usd));
+        }
     }
 }
 
@@ -87,17 +146,19 @@ fn push_create_value_transfers(
 ) {
     for transfer in transfers.iter() {
         let pk: HashMap<String, String> = HashMap::from([
+            (String::from("dao_id"), transfer.dao_id.to_string()),
             (String::from("hash"), transfer.hash.to_string()),
-            (String::from("call_index"), transfer.call_index.to_string()),
+            (String::from("ordinal"), transfer.ordinal.to_string()),
         ]);
 
-        changes
+        let change = changes
             .push_change_composite(
-                "value_transfers",
+                table_name("value_transfers"),
                 pk,
-                transfer.call_index as u64,
+                transfer.ordinal,
                 Operation::Create,
             )
+            .change("call_index", (None, transfer.call_index))
             .change("tx_index", (None, transfer.tx_index))
             .change("from_address", (None, &transfer.from.to_string()))
             .change("to_address", (None, &transfer.to.to_string()))
@@ -105,18 +166,552 @@ fn push_create_value_transfers(
             .change("value", (None, &transfer.value))
             .change("timestamp", (None, block_timestamp))
             .change("reason", (None, transfer.reason));
+        if !transfer.value_usd.is_empty() {
+            change.change("value_usd", (None, &transfer.value_usd));
+        }
     }
 }
 
 fn push_create_call_traces(changes: &mut DatabaseChanges, call_traces: Vec<CallTraceRecord>) {
     for call in call_traces.iter() {
         let pk: HashMap<String, String> = HashMap::from([
+            (String::from("dao_id"), call.dao_id.to_string()),
             (String::from("tx_hash"), call.hash.to_string()),
             (String::from("index"), call.index.to_string()),
         ]);
 
         changes
-            .push_change_composite("call_traces", pk, call.index as u64, Operation::Create)
+            .push_change_composite(
+                table_name("call_traces"),
+                pk,
+                call.index as u64,
+                Operation::Create,
+            )
             .change("trace", (None, &call.traces.to_string()));
     }
 }
+
+// One price per token and block: the last answer of its feed in the block
+pub fn feed_prices_to_database_changes(changes: &mut DatabaseChanges, feed_prices: FeedPrices) {
+    let mut last_prices: BTreeMap<&String, &FeedPrice> = BTreeMap::new();
+    for price in feed_prices.prices.iter() {
+        last_prices.insert(&price.token_address, price);
+    }
+
+    for (token_address, price) in last_prices {
+        let pk: HashMap<String, String> = HashMap::from([
+            (String::from("token_address"), token_address.to_string()),
+            (
+                String::from("block_number"),
+                feed_prices.block_number.to_string(),
+            ),
+        ]);
+
+        changes
+            .push_change_composite(
+                table_name("token_prices"),
+                pk,
+                price.ordinal,
+                Operation::Create,
+            )
+            .change("timestamp", (None, feed_prices.block_timestamp))
+            .change("price", (None, &price.price))
+            .change("feed_address", (None, &price.feed_address))
+            .change("round_id", (None, &price.round_id))
+            .change("updated_at", (None, price.updated_at));
+    }
+}
+
+pub fn net_worths_to_database_changes(changes: &mut DatabaseChanges, net_worths: NetWorths) {
+    for (ordinal, value) in net_worths.values.iter().enumerate() {
+        let pk: HashMap<String, String> = HashMap::from([
+            (String::from("dao_id"), value.dao_id.to_string()),
+            (String::from("day"), net_worths.day.to_string()),
+            (String::from("address"), value.address.to_string()),
+            (
+                String::from("token_address"),
+                value.token_address.to_string(),
+            ),
+        ]);
+
+        changes
+            .push_change_composite(
+                table_name("net_worth"),
+                pk,
+                ordinal as u64,
+                Operation::Create,
+            )
+            .change("block_number", (None, net_worths.block_number))
+            .change("timestamp", (None, net_worths.block_timestamp))
+            .change("balance", (None, &value.balance))
+            .change("value_usd", (None, &value.value_usd));
+    }
+}
+
+// The first snapshot of an account and token in a period creates its row, later ones update it
+pub fn period_snapshots_to_database_changes(
+    changes: &mut DatabaseChanges,
+    period_snapshots: PeriodSnapshots,
+) {
+    for (ordinal, snapshot) in period_snapshots.snapshots.iter().enumerate() {
+        let table = match snapshot.period.as_str() {
+            "month" => "monthly_balances",
+            _ => "daily_balances",
+        };
+        let operation = if snapshot.first {
+            Operation::Create
+        } else {
+            Operation::Update
+        };
+        let pk: HashMap<String, String> = HashMap::from([
+            (String::from("dao_id"), snapshot.dao_id.to_string()),
+            (String::from("start_date"), snapshot.start_date.to_string()),
+            (String::from("address"), snapshot.address.to_string()),
+            (
+                String::from("token_address"),
+                snapshot.token_address.to_string(),
+            ),
+        ]);
+
+        changes
+            .push_change_composite(table_name(table), pk, ordinal as u64, operation)
+            .change("opening_balance", (None, &snapshot.opening_balance))
+            .change("inflow", (None, &snapshot.inflow))
+            .change("outflow", (None, &snapshot.outflow))
+            .change("fees", (None, &snapshot.fees))
+            .change("closing_balance", (None, &snapshot.closing_balance))
+            .change("block_number", (None, period_snapshots.block_number))
+            .change("timestamp", (None, period_snapshots.block_timestamp));
+    }
+}
+
+pub fn delegations_to_database_changes(changes: &mut DatabaseChanges, delegations: Delegations) {
+    for change in delegations.delegate_changes.iter() {
+        let pk: HashMap<String, String> = HashMap::from([
+            (String::from("dao_id"), change.dao_id.to_string()),
+            (String::from("tx_hash"), change.tx_hash.to_string()),
+            (String::from("log_index"), change.log_index.to_string()),
+        ]);
+
+        changes
+            .push_change_composite(
+                table_name("delegate_changes"),
+                pk,
+                change.ordinal,
+                Operation::Create,
+            )
+            .change("token_address", (None, &change.token_address))
+            .change("block_number", (None, delegations.block_number))
+            .change("timestamp", (None, delegations.block_timestamp))
+            .change("ordinal", (None, change.ordinal))
+            .change("delegator", (None, &change.delegator))
+            .change("from_delegate", (None, &change.from_delegate))
+            .change("to_delegate", (None, &change.to_delegate));
+    }
+
+    // The votes of a delegate at the end of the block
+    let mut last_votes: BTreeMap<(&String, &String, &String), &DelegateVotesChange> =
+        BTreeMap::new();
+    for change in delegations.votes_changes.iter() {
+        last_votes.insert(
+            (&change.dao_id, &change.token_address, &change.delegate),
+            change,
+        );
+    }
+
+    for ((dao_id, token_address, delegate), change) in last_votes {
+        let pk: HashMap<String, String> = HashMap::from([
+            (String::from("dao_id"), dao_id.to_string()),
+            (String::from("token_address"), token_address.to_string()),
+            (String::from("delegate"), delegate.to_string()),
+            (
+                String::from("block_number"),
+                delegations.block_number.to_string(),
+            ),
+        ]);
+
+        changes
+            .push_change_composite(
+                table_name("voting_power"),
+                pk,
+                change.ordinal,
+                Operation::Create,
+            )
+            .change("timestamp", (None, delegations.block_timestamp))
+            .change("votes", (None, &change.new_votes))
+            .change("tx_hash", (None, &change.tx_hash));
+    }
+}
+
+pub fn governance_to_database_changes(changes: &mut DatabaseChanges, governance: GovernanceEvents) {
+    for proposal in governance.proposals.iter() {
+        let pk: HashMap<String, String> = HashMap::from([
+            (String::from("dao_id"), proposal.dao_id.to_string()),
+            (
+                String::from("governor_address"),
+                proposal.governor_address.to_string(),
+            ),
+            (
+                String::from("proposal_id"),
+                proposal.proposal_id.to_string(),
+            ),
+        ]);
+
+        changes
+            .push_change_composite(
+                table_name("proposals"),
+                pk.clone(),
+                proposal.ordinal,
+                Operation::Create,
+            )
+            .change("block_number", (None, governance.block_number))
+            .change("timestamp", (None, governance.block_timestamp))
+            .change("tx_hash", (None, &proposal.tx_hash))
+            .change("log_index", (None, proposal.log_index))
+            .change("proposer", (None, &proposal.proposer))
+            .change("vote_start", (None, &proposal.vote_start))
+            .change("vote_end", (None, &proposal.vote_end))
+            .change("description", (None, &proposal.description));
+
+        for (index, action) in proposal.actions.iter().enumerate() {
+            let mut action_pk = pk.clone();
+            action_pk.insert(String::from("action_index"), index.to_string());
+
+            changes
+                .push_change_composite(
+                    table_name("proposal_actions"),
+                    action_pk,
+                    proposal.ordinal,
+                    Operation::Create,
+                )
+                .change("target", (None, &action.target))
+                .change("value", (None, &action.value))
+                .change("signature", (None, &action.signature))
+                .change("calldata", (None, &action.calldata));
+        }
+    }
+
+    for vote in governance.votes.iter() {
+        let pk: HashMap<String, String> = HashMap::from([
+            (String::from("dao_id"), vote.dao_id.to_string()),
+            (String::from("tx_hash"), vote.tx_hash.to_string()),
+            (String::from("log_index"), vote.log_index.to_string()),
+        ]);
+
+        changes
+            .push_change_composite(table_name("votes"), pk, vote.ordinal, Operation::Create)
+            .change("governor_address", (None, &vote.governor_address))
+            .change("proposal_id", (None, &vote.proposal_id))
+            .change("block_number", (None, governance.block_number))
+            .change("timestamp", (None, governance.block_timestamp))
+            .change("voter", (None, &vote.voter))
+            .change("support", (None, vote.support))
+            .change("weight", (None, &vote.weight))
+            .change("reason", (None, &vote.reason))
+            .change("params", (None, &vote.params));
+    }
+
+    for state_change in governance.state_changes.iter() {
+        let pk: HashMap<String, String> = HashMap::from([
+            (String::from("dao_id"), state_change.dao_id.to_string()),
+            (String::from("tx_hash"), state_change.tx_hash.to_string()),
+            (
+                String::from("log_index"),
+                state_change.log_index.to_string(),
+            ),
+        ]);
+
+        changes
+            .push_change_composite(
+                table_name("proposal_state_changes"),
+                pk,
+                state_change.ordinal,
+                Operation::Create,
+            )
+            .change("governor_address", (None, &state_change.governor_address))
+            .change("proposal_id", (None, &state_change.proposal_id))
+            .change("block_number", (None, governance.block_number))
+            .change("timestamp", (None, governance.block_timestamp))
+            .change("state", (None, &state_change.state))
+            .change("eta", (None, state_change.eta));
+    }
+}
+
+pub fn timelock_operations_to_database_changes(
+    changes: &mut DatabaseChanges,
+    timelock_operations: TimelockOperations,
+) {
+    for operation in timelock_operations.operations.iter() {
+        let pk: HashMap<String, String> = HashMap::from([
+            (String::from("dao_id"), operation.dao_id.to_string()),
+            (String::from("tx_hash"), operation.tx_hash.to_string()),
+            (String::from("log_index"), operation.log_index.to_string()),
+        ]);
+
+        let change = changes
+            .push_change_composite(
+                table_name("timelock_operations"),
+                pk,
+                operation.ordinal,
+                Operation::Create,
+            )
+            .change("timelock_address", (None, &operation.timelock_address))
+            .change("operation_id", (None, &operation.operation_id))
+            .change("call_index", (None, operation.call_index))
+            .change("block_number", (None, timelock_operations.block_number))
+            .change("timestamp", (None, timelock_operations.block_timestamp))
+            .change("ordinal", (None, operation.ordinal))
+            .change("state", (None, &operation.state))
+            .change("target", (None, &operation.target))
+            .change("signature", (None, &operation.signature))
+            .change("data", (None, &operation.data))
+            .change("predecessor", (None, &operation.predecessor))
+            .change("eta", (None, operation.eta))
+            .change("transfer_to", (None, &operation.transfer_to));
+        if !operation.value.is_empty() {
+            change.change("value", (None, &operation.value));
+        }
+        if !operation.transfer_amount.is_empty() {
+            change.change("transfer_amount", (None, &operation.transfer_amount));
+        }
+    }
+}
+
+pub fn vault_events_to_database_changes(changes: &mut DatabaseChanges, vault_events: VaultEvents) {
+    for event in vault_events.events.iter() {
+        let pk: HashMap<String, String> = HashMap::from([
+            (String::from("dao_id"), event.dao_id.to_string()),
+            (String::from("tx_hash"), event.tx_hash.to_string()),
+            (String::from("log_index"), event.log_index.to_string()),
+        ]);
+
+        changes
+            .push_change_composite(
+                table_name("vault_events"),
+                pk,
+                event.ordinal,
+                Operation::Create,
+            )
+            .change("vault_address", (None, &event.vault_address))
+            .change("asset_address", (None, &event.asset_address))
+            .change("block_number", (None, vault_events.block_number))
+            .change("timestamp", (None, vault_events.block_timestamp))
+            .change("ordinal", (None, event.ordinal))
+            .change("kind", (None, &event.kind))
+            .change("sender", (None, &event.sender))
+            .change("receiver", (None, &event.receiver))
+            .change("owner", (None, &event.owner))
+            .change("assets", (None, &event.assets))
+            .change("shares", (None, &event.shares));
+    }
+}
+
+// One price per vault and block: the last Deposit or Withdraw of the vault in the block
+pub fn vault_prices_to_database_changes(changes: &mut DatabaseChanges, vault_prices: VaultPrices) {
+    let mut last_prices: BTreeMap<&String, &VaultPrice> = BTreeMap::new();
+    for price in vault_prices.prices.iter() {
+        last_prices.insert(&price.vault_address, price);
+    }
+
+    for (vault_address, price) in last_prices {
+        let pk: HashMap<String, String> = HashMap::from([
+            (String::from("vault_address"), vault_address.to_string()),
+            (
+                String::from("block_number"),
+                vault_prices.block_number.to_string(),
+            ),
+        ]);
+
+        changes
+            .push_change_composite(
+                table_name("vault_prices"),
+                pk,
+                price.ordinal,
+                Operation::Create,
+            )
+            .change("timestamp", (None, vault_prices.block_timestamp))
+            .change("assets", (None, &price.assets))
+            .change("shares", (None, &price.shares));
+    }
+}
+
+pub fn trades_to_database_changes(changes: &mut DatabaseChanges, trades: Trades) {
+    for trade in trades.trades.iter() {
+        let pk: HashMap<String, String> = HashMap::from([
+            (String::from("dao_id"), trade.dao_id.to_string()),
+            (String::from("tx_hash"), trade.tx_hash.to_string()),
+            (String::from("log_index"), trade.log_index.to_string()),
+        ]);
+
+        let change = changes
+            .push_change_composite(table_name("trades"), pk, trade.ordinal, Operation::Create)
+            .change("account", (None, &trade.account))
+            .change("block_number", (None, trades.block_number))
+            .change("timestamp", (None, trades.block_timestamp))
+            .change("ordinal", (None, trade.ordinal))
+            .change("protocol", (None, &trade.protocol))
+            .change("pool_address", (None, &trade.pool_address))
+            .change("sender", (None, &trade.sender))
+            .change("recipient", (None, &trade.recipient))
+            .change("sold_token", (None, &trade.sold_token))
+            .change("sold_amount", (None, &trade.sold_amount))
+            .change("bought_token", (None, &trade.bought_token))
+            .change("bought_amount", (None, &trade.bought_amount));
+        if !trade.price.is_empty() {
+            change.change("price", (None, &trade.price));
+        }
+        if !trade.value_usd.is_empty() {
+            change.change("value_usd", (None, &trade.value_usd));
+        }
+    }
+}
+
+pub fn lending_events_to_database_changes(
+    changes: &mut DatabaseChanges,
+    lending_events: LendingEvents,
+) {
+    for event in lending_events.events.iter() {
+        let pk: HashMap<String, String> = HashMap::from([
+            (String::from("dao_id"), event.dao_id.to_string()),
+            (String::from("tx_hash"), event.tx_hash.to_string()),
+            (String::from("log_index"), event.log_index.to_string()),
+            (String::from("side"), event.side.to_string()),
+        ]);
+
+        let change = changes
+            .push_change_composite(
+                table_name("lending_events"),
+                pk,
+                event.ordinal,
+                Operation::Create,
+            )
+            .change("account", (None, &event.account))
+            .change("block_number", (None, lending_events.block_number))
+            .change("timestamp", (None, lending_events.block_timestamp))
+            .change("ordinal", (None, event.ordinal))
+            .change("protocol", (None, &event.protocol))
+            .change("market_address", (None, &event.market_address))
+            .change("asset_address", (None, &event.asset_address))
+            .change("kind", (None, &event.kind))
+            .change("counterparty", (None, &event.counterparty));
+        if !event.amount.is_empty() {
+            change.change("amount", (None, &event.amount));
+        }
+        if !event.tokens.is_empty() {
+            change.change("tokens", (None, &event.tokens));
+        }
+        if !event.account_borrows.is_empty() {
+            change.change("account_borrows", (None, &event.account_borrows));
+        }
+    }
+}
+
+pub fn lp_events_to_database_changes(changes: &mut DatabaseChanges, lp_events: LpEvents) {
+    for event in lp_events.events.iter() {
+        let pk: HashMap<String, String> = HashMap::from([
+            (String::from("dao_id"), event.dao_id.to_string()),
+            (String::from("tx_hash"), event.tx_hash.to_string()),
+            (String::from("log_index"), event.log_index.to_string()),
+        ]);
+
+        let change = changes
+            .push_change_composite(
+                table_name("lp_events"),
+                pk,
+                event.ordinal,
+                Operation::Create,
+            )
+            .change("owner", (None, &event.owner))
+            .change("token_id", (None, &event.token_id))
+            .change("block_number", (None, lp_events.block_number))
+            .change("timestamp", (None, lp_events.block_timestamp))
+            .change("ordinal", (None, event.ordinal))
+            .change("kind", (None, &event.kind))
+            .change("pool_address", (None, &event.pool_address))
+            .change("tick_lower", (None, event.tick_lower))
+            .change("tick_upper", (None, event.tick_upper))
+            .change("token0", (None, &event.token0))
+            .change("token1", (None, &event.token1))
+            .change("amount0", (None, &event.amount0))
+            .change("amount1", (None, &event.amount1))
+            .change("recipient", (None, &event.recipient));
+        if !event.liquidity.is_empty() {
+            change.change("liquidity", (None, &event.liquidity));
+        }
+    }
+}
+
+pub fn stream_events_to_database_changes(
+    changes: &mut DatabaseChanges,
+    stream_events: StreamEvents,
+) {
+    for event in stream_events.events.iter() {
+        let pk: HashMap<String, String> = HashMap::from([
+            (String::from("dao_id"), event.dao_id.to_string()),
+            (String::from("tx_hash"), event.tx_hash.to_string()),
+            (String::from("log_index"), event.log_index.to_string()),
+        ]);
+
+        let change = changes
+            .push_change_composite(
+                table_name("stream_events"),
+                pk,
+                event.ordinal,
+                Operation::Create,
+            )
+            .change("block_number", (None, stream_events.block_number))
+            .change("timestamp", (None, stream_events.block_timestamp))
+            .change("ordinal", (None, event.ordinal))
+            .change("protocol", (None, &event.protocol))
+            .change("contract_address", (None, &event.contract_address))
+            .change("stream_id", (None, &event.stream_id))
+            .change("kind", (None, &event.kind))
+            .change("sender", (None, &event.sender))
+            .change("recipient", (None, &event.recipient))
+            .change("token_address", (None, &event.token_address))
+            .change("start_time", (None, event.start_time))
+            .change("cliff_time", (None, event.cliff_time))
+            .change("stop_time", (None, event.stop_time));
+        if !event.deposit.is_empty() {
+            change.change("deposit", (None, &event.deposit));
+        }
+        if !event.amount_per_sec.is_empty() {
+            change.change("amount_per_sec", (None, &event.amount_per_sec));
+        }
+        if !event.amount.is_empty() {
+            change.change("amount", (None, &event.amount));
+        }
+        if !event.refund.is_empty() {
+            change.change("refund", (None, &event.refund));
+        }
+    }
+}
+
+pub fn claims_to_database_changes(changes: &mut DatabaseChanges, claims: Claims) {
+    for claim in claims.claims.iter() {
+        let pk: HashMap<String, String> = HashMap::from([
+            (String::from("dao_id"), claim.dao_id.to_string()),
+            (
+                String::from("distributor_address"),
+                claim.distributor_address.to_string(),
+            ),
+            (String::from("claim_index"), claim.claim_index.to_string()),
+        ]);
+
+        changes
+            .push_change_composite(table_name("claims"), pk, claim.ordinal, Operation::Create)
+            .change("block_number", (None, claims.block_number))
+            .change("timestamp", (None, claims.block_timestamp))
+            .change("tx_hash", (None, &claim.tx_hash))
+            .change("log_index", (None, claim.log_index))
+            .change("ordinal", (None, claim.ordinal))
+            .change("token_address", (None, &claim.token_address))
+            .change("account", (None, &claim.account))
+            .change("amount", (None, &claim.amount));
+    }
+}
+
+// Tables are created with the table_prefix from frens.yaml
+fn table_name(name: &str) -> String {
+    format!("{}{}", TABLE_PREFIX, name)
+}
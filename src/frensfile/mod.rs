@@ -3,9 +3,37 @@ use serde_yaml::{self};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct FrensFile {
+    #[serde(default)]
     pub version: String,
+    pub name: Option<String>,
+    pub id: Option<String>, // optional dao_id, defaults to the name in snake case
+    #[serde(default)]
     pub tokens_issued: Vec<TokenIssued>,
+    #[serde(default)]
     pub treasury_accounts: Vec<TreasuryAccount>,
+    pub address_book: Option<Vec<AddressLabel>>,
+    pub labels_files: Option<Vec<String>>, // optional CSV files of address,name,category
+    pub factories: Option<Vec<Factory>>,
+    pub organizations: Option<Vec<FrensFile>>, // optional additional DAOs to index
+    pub organization_files: Option<Vec<String>>, // optional frens.yaml files of additional DAOs
+    pub database: Option<Database>,
+    pub token_lists: Option<Vec<String>>, // optional Uniswap format token lists, defaults to the bundled list
+    pub tokens: Option<Vec<TokenMetadata>>, // optional token metadata, overrides the token lists
+    pub price_pools: Option<Vec<PricePool>>, // optional pools used to price tokens in USD
+    pub usd_tokens: Option<Vec<String>>, // optional stable coins worth one dollar, defaults to USDC, USDT, DAI
+    pub price_feeds: Option<Vec<PriceFeed>>, // optional Chainlink USD feeds, preferred over the price pools
+    pub governance: Option<Vec<Governor>>, // optional Governor contracts whose proposals and votes are indexed
+    pub timelocks: Option<Vec<Timelock>>, // optional TimelockController or Compound Timelock contracts
+    pub rebasing_tokens: Option<Vec<RebasingToken>>, // optional share based tokens, eg: stETH, aTokens
+    pub distributors: Option<Vec<Distributor>>, // optional MerkleDistributor contracts of airdrops
+    pub streams: Option<Vec<StreamContract>>, // optional LlamaPay contracts, or Sablier deployments not built in
+}
+
+// Postgres settings, read from the top level frens.yaml only
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Database {
+    pub schema_name: Option<String>,  // optional, defaults to substream1
+    pub table_prefix: Option<String>, // optional prefix of every table, view and function
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -15,6 +43,8 @@ pub struct TokenIssued {
     // token_address
     #[serde(skip_deserializing)]
     pub token_address: [u8; 20],
+    #[serde(skip_deserializing)]
+    pub dao_id: String,
     pub token_id: Option<String>,
     pub network: Option<String>, // optional, defaults to mainnet
     pub schema: Option<String>,  // optional, defaults to erc20  TODO: use enum?
@@ -27,9 +57,250 @@ pub struct TreasuryAccount {
     pub address: String,
     pub network: Option<String>, // TODO: Not used.  implement multinetwork support
     pub initial_block: u64,
+    pub group: Option<String>, // optional working group, eg: operations, grants, payroll
+    pub tags: Option<Vec<String>>, // optional free-form tags, eg: vesting, cold-storage
+}
+
+// Factory contract whose ProxyCreation events register new treasury accounts
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Factory {
+    pub name: String,
+    pub address: String, // factory address is deserialized into address, then converted into
+    // factory_address
+    #[serde(skip_deserializing)]
+    pub factory_address: [u8; 20],
+    #[serde(skip_deserializing)]
+    pub dao_id: String,
+    pub network: Option<String>,
+    pub initial_block: u64,
+    pub treasury_only: Option<bool>, // optional, defaults to true: only track proxies created by a
+                                     // treasury account
+}
+
+// Uniswap pool whose Sync or Swap events price token0 in token1
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PricePool {
+    pub name: String,
+    pub address: String, // pool address is deserialized into address, then converted into
+    // pool_address
+    #[serde(skip_deserializing)]
+    pub pool_address: [u8; 20],
+    pub protocol: Option<String>, // optional, uniswap_v2 or uniswap_v3, defaults to uniswap_v2
+    pub token0: String,
+    pub token1: String,
+    pub decimals0: u32,
+    pub decimals1: u32,
+}
+
+// Chainlink aggregator whose AnswerUpdated events price a token in USD
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PriceFeed {
+    pub name: String,
+    pub address: String, // aggregator address, not the proxy, is deserialized into address, then
+    // converted into feed_address
+    #[serde(skip_deserializing)]
+    pub feed_address: [u8; 20],
+    pub token: String,               // ETH or the token address
+    pub decimals: Option<u32>,       // optional decimals of the answer, defaults to 8
+    pub token_decimals: Option<u32>, // optional, defaults to the decimals from the token metadata
+}
+
+// OpenZeppelin Governor or Compound GovernorBravo contract
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Governor {
+    pub name: String,
+    pub address: String, // governor address is deserialized into address, then converted into
+    // governor_address
+    #[serde(skip_deserializing)]
+    pub governor_address: [u8; 20],
+    #[serde(skip_deserializing)]
+    pub dao_id: String,
+    pub network: Option<String>,
+    pub initial_block: u64,
+}
+
+// OpenZeppelin TimelockController or Compound Timelock contract
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Timelock {
+    pub name: String,
+    pub address: String, // timelock address is deserialized into address, then converted into
+    // timelock_address
+    #[serde(skip_deserializing)]
+    pub timelock_address: [u8; 20],
+    #[serde(skip_deserializing)]
+    pub dao_id: String,
+    pub network: Option<String>,
+    pub initial_block: u64,
+}
+
+// Uniswap MerkleDistributor airdropping a token
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Distributor {
+    pub name: String,
+    pub address: String, // distributor address is deserialized into address, then converted into
+    // distributor_address
+    #[serde(skip_deserializing)]
+    pub distributor_address: [u8; 20],
+    #[serde(skip_deserializing)]
+    pub dao_id: String,
+    pub token: String,         // the airdropped token
+    pub total: Option<String>, // optional total of the airdrop in the smallest units of the token
+    pub network: Option<String>,
+    pub initial_block: u64,
+}
+
+// Token whose balances are shares times a rate, changing without Transfer events
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RebasingToken {
+    pub name: String,
+    pub address: String, // token address is deserialized into address, then converted into
+    // token_address
+    #[serde(skip_deserializing)]
+    pub token_address: [u8; 20],
+    pub protocol: String, // lido, aave_v2 or aave_v3
+}
+
+// Contract whose stream events are decoded, besides the built in Sablier deployments
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StreamContract {
+    pub name: String,
+    pub address: String, // stream contract address is deserialized into address, then converted into
+    // contract_address
+    #[serde(skip_deserializing)]
+    pub contract_address: [u8; 20],
+    pub protocol: Option<String>, // optional, llamapay, sablier_v1 or sablier_v2, defaults to llamapay
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenMetadata {
+    pub address: String,
+    pub symbol: Option<String>,
+    pub name: Option<String>,
+    pub decimals: Option<u32>,
+}
+
+// Token list in the Uniswap format: https://tokenlists.org
+#[derive(Debug, Deserialize)]
+struct TokenList {
+    tokens: Vec<TokenListEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenListEntry {
+    #[serde(rename = "chainId")]
+    chain_id: u64,
+    address: String,
+    name: String,
+    symbol: String,
+    decimals: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddressLabel {
+    pub address: String,
+    pub name: String,
+    pub category: Option<String>, // eg: exchange, contributor, vendor, protocol
 }
 
 impl FrensFile {
+    // Identifier of the DAO, carried by every record of the substream
+    #[allow(dead_code)]
+    pub fn dao_id(&self) -> String {
+        if let Some(id) = &self.id {
+            return id.to_string();
+        }
+        match &self.name {
+            Some(name) => name
+                .trim()
+                .to_lowercase()
+                .split(|c: char| !c.is_ascii_alphanumeric())
+                .filter(|w| !w.is_empty())
+                .collect::<Vec<&str>>()
+                .join("_"),
+            None => "default".to_string(),
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn schema_name(&self) -> String {
+        match self.database.as_ref().and_then(|d| d.schema_name.as_ref()) {
+            Some(schema_name) => schema_name.to_string(),
+            None => "substream1".to_string(),
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn table_prefix(&self) -> String {
+        match self.database.as_ref().and_then(|d| d.table_prefix.as_ref()) {
+            Some(table_prefix) => table_prefix.to_string(),
+            None => "".to_string(),
+        }
+    }
+
+    // Mainnet tokens of the token lists followed by the tokens sections, of this file and the organizations it
+    // contains or references.  Only the top level defaults to the bundled list.
+    #[allow(dead_code)]
+    pub fn token_metadata(&self) -> Vec<TokenMetadata> {
+        let mut token_lists: Vec<String> = Vec::new();
+        let mut overrides: Vec<TokenMetadata> = Vec::new();
+        if self.token_lists.is_none() {
+            token_lists.push("./token_lists/mainnet.tokenlist.json".to_string());
+        }
+        self.collect_token_metadata(&mut token_lists, &mut overrides);
+
+        let mut tokens: Vec<TokenMetadata> = Vec::new();
+        for file_name in token_lists.iter() {
+            tokens.extend(parse_token_list(file_name));
+        }
+        tokens.extend(overrides);
+        tokens
+    }
+
+    fn collect_token_metadata(
+        &self,
+        token_lists: &mut Vec<String>,
+        overrides: &mut Vec<TokenMetadata>,
+    ) {
+        for file_name in self.token_lists.iter().flatten() {
+            if !token_lists.contains(file_name) {
+                token_lists.push(file_name.to_string());
+            }
+        }
+        overrides.extend(self.tokens.iter().flatten().cloned());
+
+        for file_name in self.organization_files.iter().flatten() {
+            let frens_file = parse_frens_file(file_name.to_string()).unwrap();
+            frens_file.collect_token_metadata(token_lists, overrides);
+        }
+        for organization in self.organizations.iter().flatten() {
+            organization.collect_token_metadata(token_lists, overrides);
+        }
+    }
+
+    // This file's DAO, if it lists any accounts, followed by the organizations it contains or references
+    #[allow(dead_code)]
+    pub fn all_organizations(self) -> Vec<FrensFile> {
+        let mut organizations: Vec<FrensFile> = Vec::new();
+        let mut root = self;
+
+        if let Some(files) = root.organization_files.take() {
+            for file_name in files {
+                let frens_file = parse_frens_file(file_name).unwrap();
+                organizations.extend(frens_file.all_organizations());
+            }
+        }
+        if let Some(nested) = root.organizations.take() {
+            for organization in nested {
+                organizations.extend(organization.all_organizations());
+            }
+        }
+        if !root.treasury_accounts.is_empty() || !root.tokens_issued.is_empty() {
+            organizations.insert(0, root);
+        }
+
+        organizations
+    }
+
     #[allow(dead_code)]
     pub fn all_addresses(&self) -> Vec<String> {
         let mut all_addrs: Vec<String> = Vec::new();
@@ -47,6 +318,21 @@ impl FrensFile {
         all_addrs.extend(issued_addrs);
         all_addrs
     }
+
+    // Counterparty labels from the address_book section followed by the labels files
+    #[allow(dead_code)]
+    pub fn address_labels(&self) -> Vec<AddressLabel> {
+        let mut labels: Vec<AddressLabel> = Vec::new();
+        if let Some(files) = &self.labels_files {
+            for file_name in files.iter() {
+                labels.extend(parse_labels_file(file_name));
+            }
+        }
+        if let Some(address_book) = &self.address_book {
+            labels.extend(address_book.iter().cloned());
+        }
+        labels
+    }
 }
 
 #[allow(dead_code)]
@@ -56,3 +342,97 @@ pub fn parse_frens_file(file_name: String) -> Option<FrensFile> {
 
     Some(frens_file)
 }
+
+// Read a Uniswap format token list, keeping the mainnet tokens
+#[allow(dead_code)]
+pub fn parse_token_list(file_name: &String) -> Vec<TokenMetadata> {
+    let f = std::fs::File::open(file_name)
+        .unwrap_or_else(|_| panic!("Could not open token list {}", file_name));
+    let token_list: TokenList = serde_json::from_reader(f)
+        .unwrap_or_else(|e| panic!("Could not read token list {}: {}", file_name, e));
+
+    token_list
+        .tokens
+        .into_iter()
+        .filter(|t| t.chain_id == 1)
+        .map(|t| TokenMetadata {
+            address: t.address,
+            symbol: Some(t.symbol),
+            name: Some(t.name),
+            decimals: Some(t.decimals),
+        })
+        .collect()
+}
+
+// Read a CSV labels file with lines of: address,name,category
+// A header line starting with `address` is skipped.  Fields may be quoted, with "" for a quote inside them.  An
+// unquoted name may still contain commas when the line has a category, which is then the last field.
+#[allow(dead_code)]
+pub fn parse_labels_file(file_name: &String) -> Vec<AddressLabel> {
+    let contents = std::fs::read_to_string(file_name)
+        .unwrap_or_else(|_| panic!("Could not open labels file {}", file_name));
+
+    contents
+        .lines()
+        .enumerate()
+        .map(|(i, l)| (i + 1, l.trim()))
+        .filter(|(_, l)| !l.is_empty() && !l.starts_with('#') && !l.starts_with("address"))
+        .map(|(line_number, l)| {
+            let fields = split_csv_line(l);
+            let (name, category) = match fields.len() {
+                2 => (fields[1].trim().to_string(), None),
+                n if n > 2 => (
+                    fields[1..n - 1].join(",").trim().to_string(),
+                    Some(fields[n - 1].trim().to_string()),
+                ),
+                _ => panic!(
+                    "Invalid line {} in labels file {}: {}",
+                    line_number, file_name, l
+                ),
+            };
+            let address = fields[0].trim().to_lowercase();
+            let valid = address.len() == 42
+                && address.starts_with("0x")
+                && address[2..].chars().all(|c| c.is_ascii_hexdigit());
+            if !valid {
+                panic!(
+                    "Invalid address {} on line {} of labels file {}",
+                    fields[0].trim(),
+                    line_number,
+                    file_name
+                );
+            }
+            AddressLabel {
+                address,
+                name,
+                category: category.filter(|c| !c.is_empty()),
+            }
+        })
+        .collect()
+}
+
+// Split a CSV line into fields, respecting double quotes
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields: Vec<String> = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => {
+                fields.push(field.to_string());
+                field.clear();
+            }
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+
+    fields
+}
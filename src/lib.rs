@@ -8,15 +8,33 @@ mod settings;
 #[macro_use]
 extern crate lazy_static;
 
-use pb::frenscan::{CallTrace, CallTraceRecord, TokenTransfer, Transfers, ValueTransfer};
-use settings::{TOKENS_ISSUED, TREASURY_ADDRESSES};
+use pb::frenscan::{
+    AccountValue, CallTrace, CallTraceRecord, Claim, Claims, DelegateChange, DelegateVotesChange,
+    Delegations, DiscoveredAccount, DiscoveredAccounts, FeedPrice, FeedPrices, GovernanceEvents,
+    LendingEvent, LendingEvents, LpEvent, LpEvents, NetWorths, PeriodSnapshot, PeriodSnapshots,
+    PoolPrice, PoolPrices, Proposal, ProposalAction, ProposalStateChange, ShareChange,
+    ShareChanges, ShareRate, StreamEvent, StreamEvents, TimelockOperation, TimelockOperations,
+    TokenTransfer, TradeRecord, Trades, Transfers, ValueTransfer, VaultEvent, VaultEvents,
+    VaultPrice, VaultPrices, Vote,
+};
+use pb::frenscan_v2 as v2;
+use settings::{
+    DISTRIBUTORS, FACTORIES, GOVERNORS, PRICE_FEEDS, PRICE_POOLS, REBASING_TOKENS,
+    STREAM_CONTRACTS, TIMELOCKS, TOKENS_ISSUED, TREASURY_ACCOUNT_DAOS, TREASURY_ADDRESSES,
+    USD_TOKENS,
+};
+use std::collections::{BTreeSet, HashMap};
+use std::str::FromStr;
 
 use substreams::errors::Error;
+use substreams::pb::substreams::store_delta::Operation as StoreDeltaOperation;
 use substreams::prelude::*;
+use substreams::scalar::BigDecimal;
+use substreams::store::{DeltaProto, Deltas};
 use substreams::{log, Hex};
 use substreams_database_change::pb::database::DatabaseChanges;
 use substreams_ethereum::pb::eth::v2 as eth;
-use substreams_ethereum::{Event, NULL_ADDRESS};
+use substreams_ethereum::{Event, Function, NULL_ADDRESS};
 
 use abi::erc1155::events::TransferBatch as ERC1155TransferBatchEvent;
 use abi::erc1155::events::TransferSingle as ERC1155TransferSingleEvent;
@@ -26,174 +44,3054 @@ use abi::erc721::events::Transfer as ERC721TransferEvent;
 use abi::weth::events::Deposit as WETHDepositEvent;
 use abi::weth::events::Withdrawal as WETHWithdrawlEvent;
 
+use abi::safe_proxy_factory::events::ProxyCreation as SafeProxyCreationEvent;
+
+use abi::uniswap_v2_pair::events::Swap as UniswapV2SwapEvent;
+use abi::uniswap_v2_pair::events::Sync as UniswapV2SyncEvent;
+use abi::uniswap_v3_pool::events::Burn as UniswapV3BurnEvent;
+use abi::uniswap_v3_pool::events::Collect as UniswapV3CollectEvent;
+use abi::uniswap_v3_pool::events::Mint as UniswapV3MintEvent;
+use abi::uniswap_v3_pool::events::Swap as UniswapV3SwapEvent;
+use abi::uniswap_v3_position_manager::events::Collect as UniswapV3PositionCollectEvent;
+use abi::uniswap_v3_position_manager::events::DecreaseLiquidity as UniswapV3DecreaseLiquidityEvent;
+use abi::uniswap_v3_position_manager::events::IncreaseLiquidity as UniswapV3IncreaseLiquidityEvent;
+
+use abi::balancer_vault::events::Swap as BalancerSwapEvent;
+use abi::curve_pool::events::TokenExchange as CurveTokenExchangeEvent;
+
+use abi::chainlink_aggregator::events::AnswerUpdated as ChainlinkAnswerUpdatedEvent;
+
+use abi::erc20_votes::events::DelegateChanged as ERC20VotesDelegateChangedEvent;
+use abi::erc20_votes::events::DelegateVotesChanged as ERC20VotesDelegateVotesChangedEvent;
+
+use abi::governor::events::ProposalCanceled as GovernorProposalCanceledEvent;
+use abi::governor::events::ProposalCreated as GovernorProposalCreatedEvent;
+use abi::governor::events::ProposalExecuted as GovernorProposalExecutedEvent;
+use abi::governor::events::ProposalQueued as GovernorProposalQueuedEvent;
+use abi::governor::events::VoteCast as GovernorVoteCastEvent;
+use abi::governor::events::VoteCastWithParams as GovernorVoteCastWithParamsEvent;
+
+use abi::compound_timelock::events::CancelTransaction as CompoundTimelockCancelTransactionEvent;
+use abi::compound_timelock::events::ExecuteTransaction as CompoundTimelockExecuteTransactionEvent;
+use abi::compound_timelock::events::QueueTransaction as CompoundTimelockQueueTransactionEvent;
+use abi::erc20::functions::Transfer as ERC20TransferFunction;
+use abi::timelock_controller::events::CallExecuted as TimelockCallExecutedEvent;
+use abi::timelock_controller::events::CallScheduled as TimelockCallScheduledEvent;
+use abi::timelock_controller::events::Cancelled as TimelockCancelledEvent;
+
+use abi::aave_v2_atoken::events::BalanceTransfer as AaveV2BalanceTransferEvent;
+use abi::aave_v2_atoken::events::Burn as AaveV2BurnEvent;
+use abi::aave_v2_atoken::events::Mint as AaveV2MintEvent;
+use abi::aave_v3_atoken::events::BalanceTransfer as AaveV3BalanceTransferEvent;
+use abi::aave_v3_atoken::events::Burn as AaveV3BurnEvent;
+use abi::aave_v3_atoken::events::Mint as AaveV3MintEvent;
+use abi::lido::events::TokenRebased as LidoTokenRebasedEvent;
+use abi::lido::events::TransferShares as LidoTransferSharesEvent;
+
+use abi::erc4626::events::Deposit as ERC4626DepositEvent;
+use abi::erc4626::events::Withdraw as ERC4626WithdrawEvent;
+
+use abi::aave_v2_pool::events::Borrow as AaveV2BorrowEvent;
+use abi::aave_v2_pool::events::Deposit as AaveV2DepositEvent;
+use abi::aave_v2_pool::events::LiquidationCall as AaveLiquidationCallEvent;
+use abi::aave_v2_pool::events::Repay as AaveV2RepayEvent;
+use abi::aave_v2_pool::events::Withdraw as AaveWithdrawEvent;
+use abi::aave_v3_pool::events::Borrow as AaveV3BorrowEvent;
+use abi::aave_v3_pool::events::Repay as AaveV3RepayEvent;
+use abi::aave_v3_pool::events::Supply as AaveV3SupplyEvent;
+use abi::compound_ctoken::events::Borrow as CompoundBorrowEvent;
+use abi::compound_ctoken::events::LiquidateBorrow as CompoundLiquidateBorrowEvent;
+use abi::compound_ctoken::events::Mint as CompoundMintEvent;
+use abi::compound_ctoken::events::Redeem as CompoundRedeemEvent;
+use abi::compound_ctoken::events::RepayBorrow as CompoundRepayBorrowEvent;
+
+use abi::llamapay::events::StreamCancelled as LlamaPayStreamCancelledEvent;
+use abi::llamapay::events::StreamCreated as LlamaPayStreamCreatedEvent;
+use abi::llamapay::events::StreamCreatedWithReason as LlamaPayStreamCreatedWithReasonEvent;
+use abi::llamapay::events::StreamPaused as LlamaPayStreamPausedEvent;
+use abi::llamapay::events::Withdraw as LlamaPayWithdrawEvent;
+use abi::sablier_v1::events::CancelStream as SablierV1CancelStreamEvent;
+use abi::sablier_v1::events::CreateStream as SablierV1CreateStreamEvent;
+use abi::sablier_v1::events::WithdrawFromStream as SablierV1WithdrawFromStreamEvent;
+use abi::sablier_v2_lockup_linear::events::CancelLockupStream as SablierV2CancelLockupStreamEvent;
+use abi::sablier_v2_lockup_linear::events::CreateLockupLinearStream as SablierV2CreateLockupLinearStreamEvent;
+use abi::sablier_v2_lockup_linear::events::WithdrawFromLockupStream as SablierV2WithdrawFromLockupStreamEvent;
+
+use abi::merkle_distributor::events::Claimed as MerkleDistributorClaimedEvent;
+
+// ETH is priced as WETH
+const WETH_ADDRESS: &str = "c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2";
+
+// 2^192, to convert the sqrtPriceX96 of Uniswap V3 pools
+const Q192: &str = "6277101735386680763835789423207666416102355444464034512896";
+
+// 1e27, the unit of the liquidity index of Aave
+const RAY: &str = "1000000000000000000000000000";
+
+// Balancer V2 Vault, which emits the Swap events of every Balancer pool
+const BALANCER_VAULT: &str = "ba12222222228d8ba445958a75a0704d566bf2c8";
+
+// Aave lending pools, which emit the events of every reserve
+const AAVE_V2_POOL: &str = "7d2768de32b0b80b7a3454c06bdac94a69ddc7a9";
+const AAVE_V3_POOL: &str = "87870bca3f3fd6335c3f4ce8392d69350b4fa4e2";
+
+// Compound cETH, whose underlying asset is ETH
+const CETH_ADDRESS: &str = "4ddc2d193948926d02f9b1fe9e1daa0718270ed5";
+
+// Compound V2 markets.  Other contracts may emit the same events, so only these are decoded
+const COMPOUND_CTOKENS: [&str; 20] = [
+    "4ddc2d193948926d02f9b1fe9e1daa0718270ed5", // cETH
+    "5d3a536e4d6dbd6114cc1ead35777bab948e3643", // cDAI
+    "39aa39c021dfbae8fac545936693ac917d5e7563", // cUSDC
+    "f650c3d88d12db855b8bf7d11be6c55a4e07dcc9", // cUSDT
+    "ccf4429db6322d5c611ee964527d42e5d685dd6a", // cWBTC2
+    "c11b1268c1a384e55c48c2391d8d480264a3a7f4", // cWBTC, deprecated
+    "6c8c6b02e7b2be14d4fa6022dfd6d75921d90e4e", // cBAT
+    "b3319f5d18bc0d84dd1b4825dcde5d5f7266d407", // cZRX
+    "158079ee67fce2f58472a96584a73c7ab9ac95c1", // cREP
+    "f5dce57282a584d2746faf1593d3121fcac444dc", // cSAI
+    "35a18000230da775cac24873d00ff85bccded550", // cUNI
+    "70e36f6bf80a52b3b46b3af8e106cc0ed743e8e4", // cCOMP
+    "face851a4921ce59e912d19329929ce6da6eb0c7", // cLINK
+    "12392f67bdf24fae0af363c24ac620a2f67dad86", // cTUSD
+    "e65cdb6479bac1e22340e4e755fae7e509ecd06c", // cAAVE
+    "4b0181102a0112a2ef11abee5563bb4a3176c9d7", // cSUSHI
+    "95b4ef2869ebd94beb4eee400a99824bf5dc325b", // cMKR
+    "80a2ae356fc9ef4305676f7a3e2ed04e12c33946", // cYFI
+    "041171993284df560249b57358f931d9eb7b925d", // cUSDP
+    "7713dd9ca933848f6819f38b8352d9a15ea73f67", // cFEI
+];
+
+// Uniswap V3 NonfungiblePositionManager, owner of the pool positions behind the LP NFTs
+const UNISWAP_V3_POSITION_MANAGER: &str = "c36442b4a4522e871399cd717abdd847ab11fe88";
+
+// Sablier V1.0 and V1.1, and Sablier V2 LockupLinear V1.1, whose events are decoded without configuration
+const SABLIER_V1: [&str; 2] = [
+    "a4fc358455febe425536fd1878be67ffdbdec59a",
+    "cd18eaa163733da39c232722cbc4e8940b1d8888",
+];
+const SABLIER_V2_LOCKUP_LINEAR: &str = "afb979d9afad1ad27c5eff4e27226e3ab9e5dcc9";
+
 substreams_ethereum::init!();
 
-/// Extracts transfers events from the contract(s)
-#[substreams::handlers::map]
-fn map_blocks(blk: eth::Block) -> Result<Transfers, substreams::errors::Error> {
-    let mut value_transfers: Vec<ValueTransfer> = Vec::new();
-    let mut token_transfers: Vec<TokenTransfer> = Vec::new();
-    let mut call_traces: Vec<CallTraceRecord> = Vec::new();
-    let mut issued_transfers: Vec<TokenTransfer> = Vec::new();
+/// Extracts contracts created by treasury accounts or registered through a configured factory, known or not
+#[substreams::handlers::map]
+fn map_account_creations(blk: eth::Block) -> Result<DiscoveredAccounts, substreams::errors::Error> {
+    let mut accounts: Vec<DiscoveredAccount> = Vec::new();
+
+    for trace in blk.transaction_traces.iter() {
+        for call in trace.calls.iter().filter(|c| c.state_reverted == false) {
+            let caller_dao_ids = treasury_dao_ids(&Hex(&call.caller).to_string());
+            accounts.extend(call_creations(blk.number, trace, call, &caller_dao_ids));
+        }
+    }
+
+    Ok(DiscoveredAccounts {
+        block_number: blk.number,
+        accounts: unique_creations(accounts),
+    })
+}
+
+/// Keeps the accounts created by treasury accounts or through factories, keyed by dao_id:address
+#[substreams::handlers::store]
+fn store_account_creations(
+    creations: DiscoveredAccounts,
+    store: StoreSetIfNotExistsProto<DiscoveredAccount>,
+) {
+    for account in creations.accounts.iter() {
+        store.set_if_not_exists(
+            0,
+            format!("{}:{}", account.dao_id, account.address),
+            account,
+        );
+    }
+}
+
+/// Extracts contracts created by the accounts of map_account_creations, or registered by them through a factory.
+/// A module cannot read the store it feeds, so the contracts these create in turn are not discovered
+#[substreams::handlers::map]
+fn map_nested_creations(
+    blk: eth::Block,
+    created_accounts: StoreGetProto<DiscoveredAccount>,
+) -> Result<DiscoveredAccounts, substreams::errors::Error> {
+    let mut accounts: Vec<DiscoveredAccount> = Vec::new();
+
+    for trace in blk.transaction_traces.iter() {
+        for call in trace.calls.iter().filter(|c| c.state_reverted == false) {
+            let caller_dao_ids =
+                discovered_dao_ids(&Hex(&call.caller).to_string(), &created_accounts);
+            if caller_dao_ids.is_empty() {
+                continue;
+            }
+            accounts.extend(call_creations(blk.number, trace, call, &caller_dao_ids));
+        }
+    }
+
+    Ok(DiscoveredAccounts {
+        block_number: blk.number,
+        accounts: unique_creations(accounts),
+    })
+}
+
+/// Keeps the set of discovered treasury accounts, keyed by dao_id:address
+#[substreams::handlers::store]
+fn store_discovered_accounts(
+    creations: DiscoveredAccounts,
+    nested_creations: DiscoveredAccounts,
+    store: StoreSetIfNotExistsProto<DiscoveredAccount>,
+) {
+    for account in creations
+        .accounts
+        .iter()
+        .chain(nested_creations.accounts.iter())
+    {
+        store.set_if_not_exists(
+            0,
+            format!("{}:{}", account.dao_id, account.address),
+            account,
+        );
+    }
+}
+
+/// Extracts the first discovery of each account.  A contract created again at a known address, or a proxy
+/// registered again by a factory, is left out
+#[substreams::handlers::map]
+fn map_discovered_accounts(
+    creations: DiscoveredAccounts,
+    discovered: Deltas<DeltaProto<DiscoveredAccount>>,
+) -> Result<DiscoveredAccounts, substreams::errors::Error> {
+    // set_if_not_exists only has deltas for the keys it creates
+    let accounts: Vec<DiscoveredAccount> = discovered
+        .deltas
+        .into_iter()
+        .filter(|d| d.operation == StoreDeltaOperation::Create)
+        .map(|d| d.new_value)
+        .collect();
+
+    Ok(DiscoveredAccounts {
+        block_number: creations.block_number,
+        accounts: accounts,
+    })
+}
+
+/// Extracts the prices of the price pools from their Sync and Swap events
+#[substreams::handlers::map]
+fn map_pool_prices(blk: eth::Block) -> Result<PoolPrices, substreams::errors::Error> {
+    let mut prices: Vec<PoolPrice> = Vec::new();
+
+    for trace in blk.transaction_traces.iter() {
+        for call in trace.calls.iter().filter(|c| c.state_reverted == false) {
+            for log in call.logs.iter() {
+                for pool in PRICE_POOLS
+                    .iter()
+                    .filter(|p| p.pool_address == &log.address[..20])
+                {
+                    let price0 = match pool.protocol.as_deref() {
+                        Some("uniswap_v3") => UniswapV3SwapEvent::match_and_decode(log)
+                            .map(|e| sqrt_price_x96_to_price(&e.sqrt_price_x96)),
+                        _ => UniswapV2SyncEvent::match_and_decode(log)
+                            .filter(|e| e.reserve0 != BigInt::zero())
+                            .map(|e| BigDecimal::from(e.reserve1) / BigDecimal::from(e.reserve0)),
+                    };
+
+                    if let Some(price0) = price0 {
+                        prices.push(PoolPrice {
+                            pool_address: Hex(&pool.pool_address).to_string(),
+                            token0: pool.token0.to_string(),
+                            token1: pool.token1.to_string(),
+                            price0: (price0 * exponent_to_big_decimal(pool.decimals0)
+                                / exponent_to_big_decimal(pool.decimals1))
+                            .to_string(),
+                            ordinal: log.ordinal,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(PoolPrices {
+        block_number: blk.number,
+        prices: prices,
+    })
+}
+
+/// Keeps the last price of each price pool, keyed by pool address
+#[substreams::handlers::store]
+fn store_pool_prices(pool_prices: PoolPrices, store: StoreSetBigDecimal) {
+    for price in pool_prices.prices.iter() {
+        store.set(
+            price.ordinal,
+            &price.pool_address,
+            &BigDecimal::from_str(&price.price0).unwrap(),
+        );
+    }
+}
+
+/// Extracts the USD prices of the Chainlink feeds from their AnswerUpdated events
+#[substreams::handlers::map]
+fn map_feed_prices(blk: eth::Block) -> Result<FeedPrices, substreams::errors::Error> {
+    let mut prices: Vec<FeedPrice> = Vec::new();
+
+    for trace in blk.transaction_traces.iter() {
+        for call in trace.calls.iter().filter(|c| c.state_reverted == false) {
+            for log in call.logs.iter() {
+                for feed in PRICE_FEEDS
+                    .iter()
+                    .filter(|f| f.feed_address == &log.address[..20])
+                {
+                    if let Some(event) = ChainlinkAnswerUpdatedEvent::match_and_decode(log) {
+                        let price = BigDecimal::from(event.current)
+                            / exponent_to_big_decimal(feed.decimals.unwrap_or(8));
+                        prices.push(FeedPrice {
+                            feed_address: Hex(&feed.feed_address).to_string(),
+                            token_address: feed.token.to_string(),
+                            price: price.to_string(),
+                            round_id: event.round_id.to_string(),
+                            updated_at: checked_i64(&event.updated_at).unwrap_or(0),
+                            ordinal: log.ordinal,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    let block_timestamp: i64 = match blk.header {
+        Some(header) => header.timestamp.unwrap().seconds,
+        None => 0,
+    };
+
+    prices.sort_by_key(|p| p.ordinal);
+
+    Ok(FeedPrices {
+        block_number: blk.number,
+        block_timestamp: block_timestamp,
+        prices: prices,
+    })
+}
+
+/// Keeps the last price of each feed, keyed by the token it prices
+#[substreams::handlers::store]
+fn store_feed_prices(feed_prices: FeedPrices, store: StoreSetBigDecimal) {
+    for price in feed_prices.prices.iter() {
+        store.set(
+            price.ordinal,
+            &price.token_address,
+            &BigDecimal::from_str(&price.price).unwrap(),
+        );
+    }
+}
+
+/// Extracts the share changes of the DAO accounts in the rebasing tokens, and the rates of their shares
+#[substreams::handlers::map]
+fn map_share_changes(
+    blk: eth::Block,
+    discovered_accounts: StoreGetProto<DiscoveredAccount>,
+) -> Result<ShareChanges, substreams::errors::Error> {
+    let mut share_changes: Vec<ShareChange> = Vec::new();
+    let mut share_rates: Vec<ShareRate> = Vec::new();
+
+    for trace in blk.transaction_traces.iter() {
+        for call in trace.calls.iter().filter(|c| c.state_reverted == false) {
+            for log in call.logs.iter() {
+                for token in REBASING_TOKENS
+                    .iter()
+                    .filter(|t| t.token_address == &log.address[..20])
+                {
+                    let (changes, rate) = decode_share_events(&token.protocol, log);
+                    for (address, shares) in changes {
+                        let address = Hex(&address).to_string();
+                        for dao_id in account_dao_ids(&address, &discovered_accounts) {
+                            share_changes.push(ShareChange {
+                                dao_id: dao_id,
+                                address: address.to_string(),
+                                token_address: Hex(&log.address).to_string(),
+                                tx_hash: Hex(&trace.hash).to_string(),
+                                log_index: log.block_index as u64,
+                                shares: shares.to_string(),
+                                ordinal: log.ordinal,
+                            });
+                        }
+                    }
+                    if let Some((numerator, denominator)) = rate {
+                        share_rates.push(ShareRate {
+                            token_address: Hex(&log.address).to_string(),
+                            tx_hash: Hex(&trace.hash).to_string(),
+                            log_index: log.block_index as u64,
+                            numerator: numerator.to_string(),
+                            denominator: denominator.to_string(),
+                            ordinal: log.ordinal,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    let block_timestamp: i64 = match blk.header {
+        Some(header) => header.timestamp.unwrap().seconds,
+        None => 0,
+    };
+
+    share_changes.sort_by_key(|c| c.ordinal);
+    share_rates.sort_by_key(|r| r.ordinal);
+
+    Ok(ShareChanges {
+        block_number: blk.number,
+        block_timestamp: block_timestamp,
+        share_changes: share_changes,
+        share_rates: share_rates,
+    })
+}
+
+/// Keeps the shares of the rebasing tokens held by the DAO accounts, keyed by dao_id:address:token_address
+#[substreams::handlers::store]
+fn store_shares(share_changes: ShareChanges, store: StoreAddBigInt) {
+    for change in share_changes.share_changes.iter() {
+        let key = format!(
+            "{}:{}:{}",
+            change.dao_id, change.address, change.token_address
+        );
+        store.add(
+            change.ordinal,
+            key,
+            &BigInt::from_str(&change.shares).unwrap(),
+        );
+    }
+}
+
+/// Keeps the last share rate of each rebasing token, keyed by token_address
+#[substreams::handlers::store]
+fn store_share_rates(share_changes: ShareChanges, store: StoreSetProto<ShareRate>) {
+    for rate in share_changes.share_rates.iter() {
+        store.set(rate.ordinal, &rate.token_address, rate);
+    }
+}
+
+/// Extracts transfers events from the contract(s)
+#[substreams::handlers::map]
+fn map_blocks(
+    blk: eth::Block,
+    discovered_accounts: StoreGetProto<DiscoveredAccount>,
+    pool_prices: StoreGetBigDecimal,
+    feed_prices: StoreGetBigDecimal,
+    share_changes: ShareChanges,
+    shares: StoreGetBigInt,
+    share_rates: StoreGetProto<ShareRate>,
+    account_counts: StoreGetInt64,
+    dao_accounts: StoreGetProto<DiscoveredAccount>,
+) -> Result<Transfers, substreams::errors::Error> {
+    let mut value_transfers: Vec<ValueTransfer> = Vec::new();
+    let mut token_transfers: Vec<TokenTransfer> = Vec::new();
+    let mut call_traces: Vec<CallTraceRecord> = Vec::new();
+    let mut issued_transfers: Vec<TokenTransfer> = Vec::new();
+
+    // check for block rewards
+    match new_value_transfer_block_rewards_from_block(&blk, &discovered_accounts) {
+        Some(transfers) => {
+            value_transfers.extend(transfers);
+        }
+        None => {}
+    }
+
+    for trace in blk.transaction_traces.iter() {
+        //Check for gas usage
+        let calls = &trace.calls;
+        let root_call = calls.into_iter().nth(0).unwrap();
+        if is_treasury_address(&root_call.caller, &discovered_accounts) {
+            match new_gas_transfer_from_call(&trace, &root_call, &discovered_accounts) {
+                Some(transfer) => {
+                    value_transfers.extend(transfer);
+                }
+                None => {}
+            }
+        }
+
+        // Check calls
+        for call in trace
+            .calls
+            .iter()
+            .filter(|c| c.call_type != eth::CallType::Delegate as i32)
+            .filter(|c| c.state_reverted == false)
+        {
+            // Check calls for value transfers
+            if is_treasury_address(&call.caller, &discovered_accounts)
+                || is_treasury_address(&call.address, &discovered_accounts)
+            {
+                match new_value_transfer_from_call(&trace, &call) {
+                    Some(transfer) => {
+                        value_transfers.push(transfer);
+                    }
+                    None => {}
+                }
+            }
+
+            // Check logs for token transfers
+            token_transfers.extend(get_erc_transfers_from_logs(
+                &call.logs,
+                &trace.hash,
+                call.index,
+                &discovered_accounts,
+            ));
+
+            // Check logs for issued token transfers
+            issued_transfers.extend(get_erc_issued_transfers_from_logs(
+                &call.logs,
+                &trace.hash,
+                call.index,
+            ));
+        }
+    }
+
+    // USD values at the price of the ordinal of each transfer.  NFTs are not priced
+    for t in token_transfers
+        .iter_mut()
+        .chain(issued_transfers.iter_mut())
+        .filter(|t| t.token_id.is_empty())
+    {
+        t.value_usd = value_usd(
+            &t.token_address,
+            &t.value,
+            t.ordinal,
+            &pool_prices,
+            &feed_prices,
+        );
+    }
+    for t in value_transfers.iter_mut() {
+        t.value_usd = value_usd(
+            &WETH_ADDRESS.to_string(),
+            &t.value,
+            t.ordinal,
+            &pool_prices,
+            &feed_prices,
+        );
+    }
+
+    // Record each transfer once for every DAO it belongs to
+    let mut token_transfers: Vec<TokenTransfer> = token_transfers
+        .into_iter()
+        .flat_map(|t| {
+            transfer_dao_ids(&t.from, &t.to, &discovered_accounts)
+                .into_iter()
+                .map(move |dao_id| TokenTransfer {
+                    dao_id: dao_id,
+                    ..t.clone()
+                })
+        })
+        .collect();
+    let mut value_transfers: Vec<ValueTransfer> = value_transfers
+        .into_iter()
+        .flat_map(|t| {
+            transfer_dao_ids(&t.from, &t.to, &discovered_accounts)
+                .into_iter()
+                .map(move |dao_id| ValueTransfer {
+                    dao_id: dao_id,
+                    ..t.clone()
+                })
+        })
+        .collect();
+
+    // Balance changes of the rebasing tokens without a Transfer event, each already for a single DAO
+    if !share_changes.share_changes.is_empty() || !share_changes.share_rates.is_empty() {
+        let mut rebases = rebase_transfers(
+            &blk,
+            &share_changes,
+            &shares,
+            &share_rates,
+            &dao_account_list(&account_counts, &dao_accounts),
+            &token_transfers,
+        );
+        for t in rebases.iter_mut() {
+            t.value_usd = value_usd(
+                &t.token_address,
+                &t.value,
+                t.ordinal,
+                &pool_prices,
+                &feed_prices,
+            );
+        }
+        token_transfers.extend(rebases);
+    }
+
+    let mut issued_transfers: Vec<TokenTransfer> = issued_transfers
+        .into_iter()
+        .flat_map(|t| {
+            let mut dao_ids: Vec<String> = TOKENS_ISSUED
+                .iter()
+                .filter(|i| Hex(&i.token_address).to_string() == t.token_address)
+                .map(|i| i.dao_id.to_string())
+                .collect();
+            dao_ids.sort();
+            dao_ids.dedup();
+            dao_ids.into_iter().map(move |dao_id| TokenTransfer {
+                dao_id: dao_id,
+                ..t.clone()
+            })
+        })
+        .collect();
+
+    // Save call traces for transactions with transfers.  A rebase may come from a transaction of anyone
+    let mut tx_hashes: Vec<(&String, &String)> = token_transfers
+        .iter()
+        .filter(|t| t.reason.is_empty())
+        .map(|t| (&t.tx_hash, &t.dao_id))
+        .collect();
+    tx_hashes.extend(issued_transfers.iter().map(|t| (&t.tx_hash, &t.dao_id)));
+    tx_hashes.extend(
+        value_transfers
+            .iter()
+            .filter(|t| {
+                let r = eth::balance_change::Reason::from_i32(t.reason).unwrap();
+                r != eth::balance_change::Reason::RewardMineBlock
+                    && r != eth::balance_change::Reason::RewardMineUncle
+            })
+            .map(|v| (&v.hash, &v.dao_id)),
+    );
+    tx_hashes.sort();
+    tx_hashes.dedup();
+
+    for trace in blk.transaction_traces.iter() {
+        let hash = Hex(&trace.hash).to_string();
+        let dao_ids: Vec<&String> = tx_hashes
+            .iter()
+            .filter(|(h, _)| **h == hash)
+            .map(|(_, dao_id)| *dao_id)
+            .collect();
+        if dao_ids.is_empty() {
+            continue;
+        }
+
+        let record = get_call_traces(trace);
+        for dao_id in dao_ids {
+            call_traces.push(CallTraceRecord {
+                dao_id: dao_id.to_string(),
+                ..record.clone()
+            });
+        }
+    }
+
+    let block_timestamp: i64 = match blk.header {
+        Some(header) => header.timestamp.unwrap().seconds,
+        None => 0,
+    };
+
+    // Firehose ordinals order ETH and token transfers within the block
+    token_transfers.sort_by_key(|t| t.ordinal);
+    value_transfers.sort_by_key(|t| t.ordinal);
+    issued_transfers.sort_by_key(|t| t.ordinal);
+
+    Ok(Transfers {
+        block_number: blk.number,
+        block_timestamp: block_timestamp,
+        value_transfers: value_transfers,
+        token_transfers: token_transfers,
+        issued_token_transfers: issued_transfers,
+        call_traces: call_traces,
+    })
+}
+
+/// Numbers the discovered accounts of each DAO, keyed by dao_id
+#[substreams::handlers::store]
+fn store_dao_account_counts(discovered: DiscoveredAccounts, store: StoreAddInt64) {
+    for account in discovered.accounts.iter() {
+        store.add(0, &account.dao_id, 1);
+    }
+}
+
+/// Keeps the discovered accounts of each DAO, keyed by dao_id:index so they can be enumerated
+#[substreams::handlers::store]
+fn store_dao_accounts(
+    discovered: DiscoveredAccounts,
+    account_counts: StoreGetInt64,
+    store: StoreSetProto<DiscoveredAccount>,
+) {
+    // The counts already include the accounts of this block
+    let mut next_index: HashMap<&String, i64> = HashMap::new();
+    for account in discovered.accounts.iter() {
+        let index = next_index.entry(&account.dao_id).or_insert_with(|| {
+            let in_block = discovered
+                .accounts
+                .iter()
+                .filter(|a| a.dao_id == account.dao_id)
+                .count() as i64;
+            account_counts.get_last(&account.dao_id).unwrap_or(0) - in_block
+        });
+        store.set(0, format!("{}:{}", account.dao_id, index), account);
+        *index += 1;
+    }
+}
+
+/// Keeps the balance of each fungible token of the DAO accounts, keyed by dao_id:address:token_address
+#[substreams::handlers::store]
+fn store_balances(
+    transfers: Transfers,
+    discovered_accounts: StoreGetProto<DiscoveredAccount>,
+    store: StoreAddBigInt,
+) {
+    for delta in balance_deltas(&transfers, &discovered_accounts) {
+        let key = format!("{}:{}:{}", delta.dao_id, delta.address, delta.token_address);
+        store.add(delta.ordinal, key, &delta.amount);
+    }
+}
+
+/// Sums the inflows, outflows and fees of the DAO accounts by UTC day and month, keyed by
+/// period:start_date:dao_id:address:token_address:field
+#[substreams::handlers::store]
+fn store_period_flows(
+    transfers: Transfers,
+    discovered_accounts: StoreGetProto<DiscoveredAccount>,
+    store: StoreAddBigInt,
+) {
+    let deltas = balance_deltas(&transfers, &discovered_accounts);
+    let mut touched: BTreeSet<String> = BTreeSet::new();
+
+    for (period, start_date) in periods(transfers.block_timestamp) {
+        for delta in deltas.iter() {
+            let prefix = format!(
+                "{}:{}:{}:{}:{}",
+                period, start_date, delta.dao_id, delta.address, delta.token_address
+            );
+            let (field, amount) = if delta.fee {
+                ("fees", BigInt::zero() - delta.amount.clone())
+            } else if delta.amount > BigInt::zero() {
+                ("inflow", delta.amount.clone())
+            } else {
+                ("outflow", BigInt::zero() - delta.amount.clone())
+            };
+            store.add(delta.ordinal, format!("{}:{}", prefix, field), &amount);
+            touched.insert(prefix);
+        }
+    }
+
+    // Number of blocks with flows, to tell the first snapshot of the period
+    for prefix in touched {
+        store.add(0, format!("{}:blocks", prefix), &BigInt::one());
+    }
+}
+
+/// Balances and flows of the DAO accounts with transfers in the block, for their UTC day and month
+#[substreams::handlers::map]
+fn map_period_snapshots(
+    transfers: Transfers,
+    discovered_accounts: StoreGetProto<DiscoveredAccount>,
+    period_flows: StoreGetBigInt,
+    balances: StoreGetBigInt,
+) -> Result<PeriodSnapshots, substreams::errors::Error> {
+    let mut accounts: BTreeSet<(String, String, String)> = BTreeSet::new();
+    for delta in balance_deltas(&transfers, &discovered_accounts) {
+        accounts.insert((delta.dao_id, delta.address, delta.token_address));
+    }
+
+    let mut snapshots: Vec<PeriodSnapshot> = Vec::new();
+    for (period, start_date) in periods(transfers.block_timestamp) {
+        for (dao_id, address, token_address) in accounts.iter() {
+            let prefix = format!(
+                "{}:{}:{}:{}:{}",
+                period, start_date, dao_id, address, token_address
+            );
+            let flow = |field: &str| {
+                period_flows
+                    .get_last(format!("{}:{}", prefix, field))
+                    .unwrap_or(BigInt::zero())
+            };
+            let (inflow, outflow, fees) = (flow("inflow"), flow("outflow"), flow("fees"));
+            let closing_balance = balances
+                .get_last(format!("{}:{}:{}", dao_id, address, token_address))
+                .unwrap_or(BigInt::zero());
+            let opening_balance =
+                closing_balance.clone() - inflow.clone() + outflow.clone() + fees.clone();
+
+            snapshots.push(PeriodSnapshot {
+                period: period.to_string(),
+                start_date: start_date.to_string(),
+                dao_id: dao_id.to_string(),
+                address: address.to_string(),
+                token_address: token_address.to_string(),
+                opening_balance: opening_balance.to_string(),
+                inflow: inflow.to_string(),
+                outflow: outflow.to_string(),
+                fees: fees.to_string(),
+                closing_balance: closing_balance.to_string(),
+                first: flow("blocks") == BigInt::one(),
+            });
+        }
+    }
+
+    Ok(PeriodSnapshots {
+        block_number: transfers.block_number,
+        block_timestamp: transfers.block_timestamp,
+        snapshots: snapshots,
+    })
+}
+
+/// Keeps the first block of each day, keyed by days since the Unix epoch
+#[substreams::handlers::store]
+fn store_days(blk: eth::Block, store: StoreSetIfNotExistsInt64) {
+    store.set_if_not_exists(0, block_day(&blk).to_string(), &(blk.number as i64));
+}
+
+/// Values the balances of the DAO accounts at the first block of each day
+#[substreams::handlers::map]
+fn map_net_worth(
+    blk: eth::Block,
+    days: StoreGetInt64,
+    account_counts: StoreGetInt64,
+    dao_accounts: StoreGetProto<DiscoveredAccount>,
+    balances: StoreGetBigInt,
+    pool_prices: StoreGetBigDecimal,
+    feed_prices: StoreGetBigDecimal,
+) -> Result<NetWorths, substreams::errors::Error> {
+    let day = block_day(&blk);
+    let mut values: Vec<AccountValue> = Vec::new();
+
+    if days.get_last(day.to_string()) == Some(blk.number as i64) {
+        let accounts = dao_account_list(&account_counts, &dao_accounts);
+        let tokens = priced_tokens();
+        for (dao_id, address) in accounts.iter() {
+            for token in tokens.iter() {
+                let balance = match balances.get_last(format!("{}:{}:{}", dao_id, address, token)) {
+                    Some(balance) if balance != BigInt::zero() => balance,
+                    _ => continue,
+                };
+                let price_token = if token == "ETH" {
+                    WETH_ADDRESS.to_string()
+                } else {
+                    token.to_string()
+                };
+                // Prices at the end of the block
+                let value_usd = value_usd(
+                    &price_token,
+                    &balance.to_string(),
+                    u64::MAX,
+                    &pool_prices,
+                    &feed_prices,
+                );
+                if value_usd.is_empty() {
+                    continue;
+                }
+                values.push(AccountValue {
+                    dao_id: dao_id.to_string(),
+                    address: address.to_string(),
+                    token_address: token.to_string(),
+                    balance: balance.to_string(),
+                    value_usd: value_usd,
+                });
+            }
+        }
+    }
+
+    let block_timestamp: i64 = match blk.header {
+        Some(header) => header.timestamp.unwrap().seconds,
+        None => 0,
+    };
+
+    Ok(NetWorths {
+        block_number: blk.number,
+        block_timestamp: block_timestamp,
+        day: day,
+        values: values,
+    })
+}
+
+/// Extracts the ERC20Votes delegation events of the issued tokens
+#[substreams::handlers::map]
+fn map_delegations(blk: eth::Block) -> Result<Delegations, substreams::errors::Error> {
+    let mut delegate_changes: Vec<DelegateChange> = Vec::new();
+    let mut votes_changes: Vec<DelegateVotesChange> = Vec::new();
+
+    for trace in blk.transaction_traces.iter() {
+        for call in trace.calls.iter().filter(|c| c.state_reverted == false) {
+            for log in call.logs.iter() {
+                for token in TOKENS_ISSUED
+                    .iter()
+                    .filter(|t| t.token_address == &log.address[..20])
+                {
+                    if let Some(event) = ERC20VotesDelegateChangedEvent::match_and_decode(log) {
+                        delegate_changes.push(DelegateChange {
+                            dao_id: token.dao_id.to_string(),
+                            token_address: Hex(&log.address).to_string(),
+                            tx_hash: Hex(&trace.hash).to_string(),
+                            log_index: log.block_index as u64,
+                            delegator: Hex(&event.delegator).to_string(),
+                            from_delegate: Hex(&event.from_delegate).to_string(),
+                            to_delegate: Hex(&event.to_delegate).to_string(),
+                            ordinal: log.ordinal,
+                        });
+                    }
+
+                    if let Some(event) = ERC20VotesDelegateVotesChangedEvent::match_and_decode(log)
+                    {
+                        votes_changes.push(DelegateVotesChange {
+                            dao_id: token.dao_id.to_string(),
+                            token_address: Hex(&log.address).to_string(),
+                            tx_hash: Hex(&trace.hash).to_string(),
+                            log_index: log.block_index as u64,
+                            delegate: Hex(&event.delegate).to_string(),
+                            previous_votes: event.previous_balance.to_string(),
+                            new_votes: event.new_balance.to_string(),
+                            ordinal: log.ordinal,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    let block_timestamp: i64 = match blk.header {
+        Some(header) => header.timestamp.unwrap().seconds,
+        None => 0,
+    };
+
+    delegate_changes.sort_by_key(|d| d.ordinal);
+    votes_changes.sort_by_key(|v| v.ordinal);
+
+    Ok(Delegations {
+        block_number: blk.number,
+        block_timestamp: block_timestamp,
+        delegate_changes: delegate_changes,
+        votes_changes: votes_changes,
+    })
+}
+
+/// Extracts the proposals, votes and proposal state changes of the governors
+#[substreams::handlers::map]
+fn map_governance(blk: eth::Block) -> Result<GovernanceEvents, substreams::errors::Error> {
+    let mut proposals: Vec<Proposal> = Vec::new();
+    let mut votes: Vec<Vote> = Vec::new();
+    let mut state_changes: Vec<ProposalStateChange> = Vec::new();
+
+    for trace in blk.transaction_traces.iter() {
+        for call in trace.calls.iter().filter(|c| c.state_reverted == false) {
+            for log in call.logs.iter() {
+                for governor in GOVERNORS
+                    .iter()
+                    .filter(|g| g.governor_address == &log.address[..20])
+                    .filter(|g| blk.number >= g.initial_block)
+                {
+                    let dao_id = governor.dao_id.to_string();
+                    let governor_address = Hex(&log.address).to_string();
+                    let tx_hash = Hex(&trace.hash).to_string();
+                    let log_index = log.block_index as u64;
+
+                    if let Some(event) = GovernorProposalCreatedEvent::match_and_decode(log) {
+                        let actions: Vec<ProposalAction> = event
+                            .targets
+                            .iter()
+                            .enumerate()
+                            .map(|(i, target)| ProposalAction {
+                                target: Hex(target).to_string(),
+                                value: match event.values.get(i) {
+                                    Some(v) => v.to_string(),
+                                    None => "0".to_string(),
+                                },
+                                signature: event.signatures.get(i).cloned().unwrap_or_default(),
+                                calldata: match event.calldatas.get(i) {
+                                    Some(c) => Hex(c).to_string(),
+                                    None => "".to_string(),
+                                },
+                            })
+                            .collect();
+
+                        proposals.push(Proposal {
+                            dao_id: dao_id,
+                            governor_address: governor_address,
+                            proposal_id: event.proposal_id.to_string(),
+                            tx_hash: tx_hash,
+                            log_index: log_index,
+                            proposer: Hex(&event.proposer).to_string(),
+                            actions: actions,
+                            vote_start: event.vote_start.to_string(),
+                            vote_end: event.vote_end.to_string(),
+                            description: event.description,
+                            ordinal: log.ordinal,
+                        });
+                        continue;
+                    }
+
+                    if let Some(event) = GovernorVoteCastEvent::match_and_decode(log) {
+                        votes.push(Vote {
+                            dao_id: dao_id,
+                            governor_address: governor_address,
+                            proposal_id: event.proposal_id.to_string(),
+                            tx_hash: tx_hash,
+                            log_index: log_index,
+                            voter: Hex(&event.voter).to_string(),
+                            support: event.support.to_u64() as u32,
+                            weight: event.weight.to_string(),
+                            reason: event.reason,
+                            params: "".to_string(),
+                            ordinal: log.ordinal,
+                        });
+                        continue;
+                    }
+
+                    if let Some(event) = GovernorVoteCastWithParamsEvent::match_and_decode(log) {
+                        votes.push(Vote {
+                            dao_id: dao_id,
+                            governor_address: governor_address,
+                            proposal_id: event.proposal_id.to_string(),
+                            tx_hash: tx_hash,
+                            log_index: log_index,
+                            voter: Hex(&event.voter).to_string(),
+                            support: event.support.to_u64() as u32,
+                            weight: event.weight.to_string(),
+                            reason: event.reason,
+                            params: Hex(&event.params).to_string(),
+                            ordinal: log.ordinal,
+                        });
+                        continue;
+                    }
+
+                    let state_change = if let Some(event) =
+                        GovernorProposalQueuedEvent::match_and_decode(log)
+                    {
+                        Some((
+                            event.proposal_id,
+                            "queued",
+                            checked_i64(&event.eta).unwrap_or(i64::MAX) as u64,
+                        ))
+                    } else if let Some(event) = GovernorProposalExecutedEvent::match_and_decode(log)
+                    {
+                        Some((event.proposal_id, "executed", 0))
+                    } else if let Some(event) = GovernorProposalCanceledEvent::match_and_decode(log)
+                    {
+                        Some((event.proposal_id, "canceled", 0))
+                    } else {
+                        None
+                    };
+
+                    if let Some((proposal_id, state, eta)) = state_change {
+                        state_changes.push(ProposalStateChange {
+                            dao_id: dao_id,
+                            governor_address: governor_address,
+                            proposal_id: proposal_id.to_string(),
+                            tx_hash: tx_hash,
+                            log_index: log_index,
+                            state: state.to_string(),
+                            eta: eta,
+                            ordinal: log.ordinal,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    let block_timestamp: i64 = match blk.header {
+        Some(header) => header.timestamp.unwrap().seconds,
+        None => 0,
+    };
+
+    proposals.sort_by_key(|p| p.ordinal);
+    votes.sort_by_key(|v| v.ordinal);
+    state_changes.sort_by_key(|s| s.ordinal);
+
+    Ok(GovernanceEvents {
+        block_number: blk.number,
+        block_timestamp: block_timestamp,
+        proposals: proposals,
+        votes: votes,
+        state_changes: state_changes,
+    })
+}
+
+/// Extracts the operations scheduled, executed and canceled by the timelocks
+#[substreams::handlers::map]
+fn map_timelock_operations(
+    blk: eth::Block,
+) -> Result<TimelockOperations, substreams::errors::Error> {
+    let block_timestamp: i64 = match &blk.header {
+        Some(header) => header.timestamp.as_ref().unwrap().seconds,
+        None => 0,
+    };
+    let mut operations: Vec<TimelockOperation> = Vec::new();
+
+    for trace in blk.transaction_traces.iter() {
+        for call in trace.calls.iter().filter(|c| c.state_reverted == false) {
+            for log in call.logs.iter() {
+                for timelock in TIMELOCKS
+                    .iter()
+                    .filter(|t| t.timelock_address == &log.address[..20])
+                    .filter(|t| blk.number >= t.initial_block)
+                {
+                    let base = TimelockOperation {
+                        dao_id: timelock.dao_id.to_string(),
+                        timelock_address: Hex(&log.address).to_string(),
+                        tx_hash: Hex(&trace.hash).to_string(),
+                        log_index: log.block_index as u64,
+                        ordinal: log.ordinal,
+                        ..Default::default()
+                    };
+
+                    // TimelockController calls carry the selector in their data
+                    if let Some(event) = TimelockCallScheduledEvent::match_and_decode(log) {
+                        // call_index is an integer column, and no batch has as many calls
+                        let call_index =
+                            match checked_i64(&event.index).filter(|i| *i <= i32::MAX as i64) {
+                                Some(index) => index as u64,
+                                None => continue,
+                            };
+                        let transfer = decode_erc20_transfer("", &event.data);
+                        operations.push(TimelockOperation {
+                            operation_id: Hex(&event.id).to_string(),
+                            call_index: call_index,
+                            state: "scheduled".to_string(),
+                            target: Hex(&event.target).to_string(),
+                            value: event.value.to_string(),
+                            data: Hex(&event.data).to_string(),
+                            predecessor: Hex(&event.predecessor).to_string(),
+                            eta: checked_i64(&event.delay)
+                                .unwrap_or(i64::MAX)
+                                .saturating_add(block_timestamp)
+                                as u64,
+                            transfer_to: transfer_to(&transfer),
+                            transfer_amount: transfer_amount(&transfer),
+                            ..base
+                        });
+                        continue;
+                    }
+
+                    if let Some(event) = TimelockCallExecutedEvent::match_and_decode(log) {
+                        let call_index =
+                            match checked_i64(&event.index).filter(|i| *i <= i32::MAX as i64) {
+                                Some(index) => index as u64,
+                                None => continue,
+                            };
+                        let transfer = decode_erc20_transfer("", &event.data);
+                        operations.push(TimelockOperation {
+                            operation_id: Hex(&event.id).to_string(),
+                            call_index: call_index,
+                            state: "executed".to_string(),
+                            target: Hex(&event.target).to_string(),
+                            value: event.value.to_string(),
+                            data: Hex(&event.data).to_string(),
+                            transfer_to: transfer_to(&transfer),
+                            transfer_amount: transfer_amount(&transfer),
+                            ..base
+                        });
+                        continue;
+                    }
+
+                    if let Some(event) = TimelockCancelledEvent::match_and_decode(log) {
+                        operations.push(TimelockOperation {
+                            operation_id: Hex(&event.id).to_string(),
+                            state: "canceled".to_string(),
+                            ..base
+                        });
+                        continue;
+                    }
+
+                    // Compound Timelock events have the same fields, so they are all decoded as QueueTransaction
+                    let state = if CompoundTimelockQueueTransactionEvent::match_log(log) {
+                        "scheduled"
+                    } else if CompoundTimelockExecuteTransactionEvent::match_log(log) {
+                        "executed"
+                    } else if CompoundTimelockCancelTransactionEvent::match_log(log) {
+                        "canceled"
+                    } else {
+                        continue;
+                    };
+
+                    if let Ok(event) = CompoundTimelockQueueTransactionEvent::decode(log) {
+                        let transfer = decode_erc20_transfer(&event.signature, &event.data);
+                        operations.push(TimelockOperation {
+                            operation_id: Hex(&event.tx_hash).to_string(),
+                            state: state.to_string(),
+                            target: Hex(&event.target).to_string(),
+                            value: event.value.to_string(),
+                            signature: event.signature,
+                            data: Hex(&event.data).to_string(),
+                            eta: checked_i64(&event.eta).unwrap_or(i64::MAX) as u64,
+                            transfer_to: transfer_to(&transfer),
+                            transfer_amount: transfer_amount(&transfer),
+                            ..base
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    operations.sort_by_key(|o| o.ordinal);
+
+    Ok(TimelockOperations {
+        block_number: blk.number,
+        block_timestamp: block_timestamp,
+        operations: operations,
+    })
+}
+
+/// Extracts the ERC-4626 deposits and withdrawals of any vault whose owner or receiver is a DAO account
+#[substreams::handlers::map]
+fn map_vault_events(
+    blk: eth::Block,
+    discovered_accounts: StoreGetProto<DiscoveredAccount>,
+) -> Result<VaultEvents, substreams::errors::Error> {
+    let mut events: Vec<VaultEvent> = Vec::new();
+
+    for trace in blk.transaction_traces.iter() {
+        for call in trace.calls.iter().filter(|c| c.state_reverted == false) {
+            for log in call.logs.iter() {
+                let vault_address = Hex(&log.address).to_string();
+                // (kind, sender, receiver, owner, assets, shares, asset sent to or received from the vault)
+                let (kind, sender, receiver, owner, assets, shares, asset_address) =
+                    if let Some(event) = ERC4626DepositEvent::match_and_decode(log) {
+                        let owner = Hex(&event.owner).to_string();
+                        let asset_address =
+                            transferred_token(trace, None, Some(&vault_address), &event.assets);
+                        (
+                            "deposit",
+                            Hex(&event.sender).to_string(),
+                            owner.to_string(),
+                            owner,
+                            event.assets,
+                            event.shares,
+                            asset_address,
+                        )
+                    } else if let Some(event) = ERC4626WithdrawEvent::match_and_decode(log) {
+                        let asset_address =
+                            transferred_token(trace, Some(&vault_address), None, &event.assets);
+                        (
+                            "withdraw",
+                            Hex(&event.sender).to_string(),
+                            Hex(&event.receiver).to_string(),
+                            Hex(&event.owner).to_string(),
+                            event.assets,
+                            event.shares,
+                            asset_address,
+                        )
+                    } else {
+                        continue;
+                    };
+
+                for dao_id in transfer_dao_ids(&owner, &receiver, &discovered_accounts) {
+                    events.push(VaultEvent {
+                        dao_id: dao_id,
+                        vault_address: vault_address.to_string(),
+                        asset_address: asset_address.to_string(),
+                        tx_hash: Hex(&trace.hash).to_string(),
+                        log_index: log.block_index as u64,
+                        kind: kind.to_string(),
+                        sender: sender.to_string(),
+                        receiver: receiver.to_string(),
+                        owner: owner.to_string(),
+                        assets: assets.to_string(),
+                        shares: shares.to_string(),
+                        ordinal: log.ordinal,
+                    });
+                }
+            }
+        }
+    }
+
+    let block_timestamp: i64 = match blk.header {
+        Some(header) => header.timestamp.unwrap().seconds,
+        None => 0,
+    };
+
+    events.sort_by_key(|e| e.ordinal);
+
+    Ok(VaultEvents {
+        block_number: blk.number,
+        block_timestamp: block_timestamp,
+        events: events,
+    })
+}
+
+/// Keeps the vaults used by the DAO accounts, keyed by vault_address, with the block of their first event
+#[substreams::handlers::store]
+fn store_vaults(vault_events: VaultEvents, store: StoreSetIfNotExistsInt64) {
+    for event in vault_events.events.iter() {
+        store.set_if_not_exists(0, &event.vault_address, &(vault_events.block_number as i64));
+    }
+}
+
+/// Assets per share of the vaults used by the DAO accounts, from the Deposit and Withdraw events of anyone
+#[substreams::handlers::map]
+fn map_vault_prices(
+    blk: eth::Block,
+    vaults: StoreGetInt64,
+) -> Result<VaultPrices, substreams::errors::Error> {
+    let mut prices: Vec<VaultPrice> = Vec::new();
+
+    for trace in blk.transaction_traces.iter() {
+        for call in trace.calls.iter().filter(|c| c.state_reverted == false) {
+            for log in call.logs.iter() {
+                let vault_address = Hex(&log.address).to_string();
+                let (assets, shares) =
+                    if let Some(event) = ERC4626DepositEvent::match_and_decode(log) {
+                        (event.assets, event.shares)
+                    } else if let Some(event) = ERC4626WithdrawEvent::match_and_decode(log) {
+                        (event.assets, event.shares)
+                    } else {
+                        continue;
+                    };
+                if shares == BigInt::zero() || vaults.get_last(&vault_address).is_none() {
+                    continue;
+                }
+
+                prices.push(VaultPrice {
+                    vault_address: vault_address,
+                    assets: assets.to_string(),
+                    shares: shares.to_string(),
+                    ordinal: log.ordinal,
+                });
+            }
+        }
+    }
+
+    let block_timestamp: i64 = match blk.header {
+        Some(header) => header.timestamp.unwrap().seconds,
+        None => 0,
+    };
+
+    prices.sort_by_key(|p| p.ordinal);
+
+    Ok(VaultPrices {
+        block_number: blk.number,
+        block_timestamp: block_timestamp,
+        prices: prices,
+    })
+}
+
+/// Pairs the sold and bought tokens of the Uniswap V2 and V3, Curve and Balancer V2 swaps made by or for the DAO
+/// accounts
+#[substreams::handlers::map]
+fn map_trades(
+    blk: eth::Block,
+    discovered_accounts: StoreGetProto<DiscoveredAccount>,
+    pool_prices: StoreGetBigDecimal,
+    feed_prices: StoreGetBigDecimal,
+) -> Result<Trades, substreams::errors::Error> {
+    let mut trades: Vec<TradeRecord> = Vec::new();
+
+    for trace in blk.transaction_traces.iter() {
+        for call in trace.calls.iter().filter(|c| c.state_reverted == false) {
+            for log in call.logs.iter() {
+                let pool_address = Hex(&log.address).to_string();
+                let mut trade = if let Some(event) = UniswapV2SwapEvent::match_and_decode(log) {
+                    let (sold_amount, bought_amount) = if event.amount0_in > BigInt::zero() {
+                        (event.amount0_in, event.amount1_out)
+                    } else {
+                        (event.amount1_in, event.amount0_out)
+                    };
+                    TradeRecord {
+                        protocol: "uniswap_v2".to_string(),
+                        sender: Hex(&event.sender).to_string(),
+                        recipient: Hex(&event.to).to_string(),
+                        sold_amount: sold_amount.to_string(),
+                        bought_amount: bought_amount.to_string(),
+                        ..Default::default()
+                    }
+                } else if let Some(event) = UniswapV3SwapEvent::match_and_decode(log) {
+                    // Amounts are signed: positive into the pool, negative out of it
+                    let (sold_amount, bought_amount) = if event.amount0 > BigInt::zero() {
+                        (event.amount0, BigInt::zero() - event.amount1)
+                    } else {
+                        (event.amount1, BigInt::zero() - event.amount0)
+                    };
+                    TradeRecord {
+                        protocol: "uniswap_v3".to_string(),
+                        sender: Hex(&event.sender).to_string(),
+                        recipient: Hex(&event.recipient).to_string(),
+                        sold_amount: sold_amount.to_string(),
+                        bought_amount: bought_amount.to_string(),
+                        ..Default::default()
+                    }
+                } else if let Some(event) = CurveTokenExchangeEvent::match_and_decode(log) {
+                    TradeRecord {
+                        protocol: "curve".to_string(),
+                        sender: Hex(&event.buyer).to_string(),
+                        recipient: Hex(&event.buyer).to_string(),
+                        sold_amount: event.tokens_sold.to_string(),
+                        bought_amount: event.tokens_bought.to_string(),
+                        ..Default::default()
+                    }
+                } else if pool_address == BALANCER_VAULT {
+                    let event = match BalancerSwapEvent::match_and_decode(log) {
+                        Some(event) => event,
+                        None => continue,
+                    };
+                    TradeRecord {
+                        protocol: "balancer_v2".to_string(),
+                        pool_address: Hex(&event.pool_id[..20]).to_string(),
+                        sender: Hex(&call.caller).to_string(),
+                        sold_token: Hex(&event.token_in).to_string(),
+                        sold_amount: event.amount_in.to_string(),
+                        bought_token: Hex(&event.token_out).to_string(),
+                        bought_amount: event.amount_out.to_string(),
+                        ..Default::default()
+                    }
+                } else {
+                    continue;
+                };
+
+                let accounts =
+                    trade_accounts(trace, &trade.recipient, &trade.sender, &discovered_accounts);
+                if accounts.is_empty() {
+                    continue;
+                }
+
+                // The pool's tokens are those transferred to and from it in the transaction
+                if trade.pool_address.is_empty() {
+                    let sold_amount = BigInt::from_str(&trade.sold_amount).unwrap();
+                    let bought_amount = BigInt::from_str(&trade.bought_amount).unwrap();
+                    trade.pool_address = pool_address.to_string();
+                    trade.sold_token =
+                        transferred_token(trace, None, Some(&pool_address), &sold_amount);
+                    trade.bought_token =
+                        transferred_token(trace, Some(&pool_address), None, &bought_amount);
+                }
+                if trade.sold_token.is_empty() || trade.bought_token.is_empty() {
+                    continue;
+                }
+
+                trade.price = trade_price(&trade);
+                trade.value_usd = value_usd(
+                    &trade.sold_token,
+                    &trade.sold_amount,
+                    log.ordinal,
+                    &pool_prices,
+                    &feed_prices,
+                );
+                if trade.value_usd.is_empty() {
+                    trade.value_usd = value_usd(
+                        &trade.bought_token,
+                        &trade.bought_amount,
+                        log.ordinal,
+                        &pool_prices,
+                        &feed_prices,
+                    );
+                }
+
+                for (dao_id, account) in accounts {
+                    trades.push(TradeRecord {
+                        dao_id: dao_id,
+                        account: account,
+                        tx_hash: Hex(&trace.hash).to_string(),
+                        log_index: log.block_index as u64,
+                        ordinal: log.ordinal,
+                        ..trade.clone()
+                    });
+                }
+            }
+        }
+    }
+
+    let block_timestamp: i64 = match blk.header {
+        Some(header) => header.timestamp.unwrap().seconds,
+        None => 0,
+    };
+
+    trades.sort_by_key(|t| t.ordinal);
+
+    Ok(Trades {
+        block_number: blk.number,
+        block_timestamp: block_timestamp,
+        trades: trades,
+    })
+}
+
+/// Supplies, withdrawals, borrows, repayments and liquidations of the DAO accounts on Aave V2 and V3 and Compound
+#[substreams::handlers::map]
+fn map_lending_events(
+    blk: eth::Block,
+    discovered_accounts: StoreGetProto<DiscoveredAccount>,
+) -> Result<LendingEvents, substreams::errors::Error> {
+    let mut events: Vec<LendingEvent> = Vec::new();
+
+    for trace in blk.transaction_traces.iter() {
+        for call in trace.calls.iter().filter(|c| c.state_reverted == false) {
+            for log in call.logs.iter() {
+                let market_address = Hex(&log.address).to_string();
+                let lending_events = if market_address == AAVE_V2_POOL {
+                    aave_lending_events("aave_v2", log)
+                } else if market_address == AAVE_V3_POOL {
+                    aave_lending_events("aave_v3", log)
+                } else if COMPOUND_CTOKENS.contains(&market_address.as_str()) {
+                    compound_lending_events(trace, log)
+                } else {
+                    continue;
+                };
+
+                for event in lending_events {
+                    for dao_id in account_dao_ids(&event.account, &discovered_accounts) {
+                        events.push(LendingEvent {
+                            dao_id: dao_id,
+                            tx_hash: Hex(&trace.hash).to_string(),
+                            log_index: log.block_index as u64,
+                            ordinal: log.ordinal,
+                            ..event.clone()
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    let block_timestamp: i64 = match blk.header {
+        Some(header) => header.timestamp.unwrap().seconds,
+        None => 0,
+    };
+
+    events.sort_by_key(|e| e.ordinal);
+
+    Ok(LendingEvents {
+        block_number: blk.number,
+        block_timestamp: block_timestamp,
+        events: events,
+    })
+}
+
+/// Keeps the owner of the Uniswap V3 LP NFTs sent to or from the DAO accounts, keyed by token_id
+#[substreams::handlers::store]
+fn store_lp_owners(
+    blk: eth::Block,
+    discovered_accounts: StoreGetProto<DiscoveredAccount>,
+    store: StoreSetString,
+) {
+    for trace in blk.transaction_traces.iter() {
+        for call in trace.calls.iter().filter(|c| c.state_reverted == false) {
+            for log in call.logs.iter() {
+                if Hex(&log.address).to_string() != UNISWAP_V3_POSITION_MANAGER {
+                    continue;
+                }
+                if let Some(event) = ERC721TransferEvent::match_and_decode(log) {
+                    if is_treasury_address(&event.from, &discovered_accounts)
+                        || is_treasury_address(&event.to, &discovered_accounts)
+                    {
+                        store.set(
+                            log.ordinal,
+                            event.token_id.to_string(),
+                            &Hex(&event.to).to_string(),
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Liquidity added and removed, and tokens collected, by the Uniswap V3 LP positions of the DAO accounts
+#[substreams::handlers::map]
+fn map_lp_events(
+    blk: eth::Block,
+    discovered_accounts: StoreGetProto<DiscoveredAccount>,
+    lp_owners: StoreGetString,
+) -> Result<LpEvents, substreams::errors::Error> {
+    let mut events: Vec<LpEvent> = Vec::new();
+    // Owners changed in this block, not yet in lp_owners
+    let mut owners: HashMap<String, String> = HashMap::new();
+
+    for trace in blk.transaction_traces.iter() {
+        let mut logs: Vec<&eth::Log> = trace
+            .calls
+            .iter()
+            .filter(|c| c.state_reverted == false)
+            .flat_map(|c| c.logs.iter())
+            .collect();
+        logs.sort_by_key(|l| l.ordinal);
+
+        for (i, log) in logs.iter().enumerate() {
+            if Hex(&log.address).to_string() != UNISWAP_V3_POSITION_MANAGER {
+                continue;
+            }
+            if let Some(event) = ERC721TransferEvent::match_and_decode(log) {
+                owners.insert(event.token_id.to_string(), Hex(&event.to).to_string());
+                continue;
+            }
+
+            let mut lp_event =
+                if let Some(event) = UniswapV3IncreaseLiquidityEvent::match_and_decode(log) {
+                    LpEvent {
+                        token_id: event.token_id.to_string(),
+                        kind: "increase".to_string(),
+                        liquidity: event.liquidity.to_string(),
+                        amount0: event.amount0.to_string(),
+                        amount1: event.amount1.to_string(),
+                        ..Default::default()
+                    }
+                } else if let Some(event) = UniswapV3DecreaseLiquidityEvent::match_and_decode(log) {
+                    LpEvent {
+                        token_id: event.token_id.to_string(),
+                        kind: "decrease".to_string(),
+                        liquidity: event.liquidity.to_string(),
+                        amount0: event.amount0.to_string(),
+                        amount1: event.amount1.to_string(),
+                        ..Default::default()
+                    }
+                } else if let Some(event) = UniswapV3PositionCollectEvent::match_and_decode(log) {
+                    LpEvent {
+                        token_id: event.token_id.to_string(),
+                        kind: "collect".to_string(),
+                        amount0: event.amount0.to_string(),
+                        amount1: event.amount1.to_string(),
+                        recipient: Hex(&event.recipient).to_string(),
+                        ..Default::default()
+                    }
+                } else {
+                    continue;
+                };
+
+            let owner = match owners.get(&lp_event.token_id) {
+                Some(owner) => owner.to_string(),
+                None => match lp_owners.get_last(&lp_event.token_id) {
+                    Some(owner) => owner,
+                    None => continue,
+                },
+            };
+            let dao_ids = account_dao_ids(&owner, &discovered_accounts);
+            if dao_ids.is_empty() {
+                continue;
+            }
+
+            let (pool_address, tick_lower, tick_upper) =
+                match lp_position_range(&logs[..i], &lp_event.kind) {
+                    Some(range) => range,
+                    None => continue,
+                };
+            lp_event.token0 = lp_event_token(trace, &lp_event, &pool_address, &lp_event.amount0);
+            lp_event.token1 = lp_event_token(trace, &lp_event, &pool_address, &lp_event.amount1);
+
+            for dao_id in dao_ids {
+                events.push(LpEvent {
+                    dao_id: dao_id,
+                    owner: owner.to_string(),
+                    tx_hash: Hex(&trace.hash).to_string(),
+                    log_index: log.block_index as u64,
+                    pool_address: pool_address.to_string(),
+                    tick_lower: tick_lower,
+                    tick_upper: tick_upper,
+                    ordinal: log.ordinal,
+                    ..lp_event.clone()
+                });
+            }
+        }
+    }
+
+    let block_timestamp: i64 = match blk.header {
+        Some(header) => header.timestamp.unwrap().seconds,
+        None => 0,
+    };
+
+    events.sort_by_key(|e| e.ordinal);
+
+    Ok(LpEvents {
+        block_number: blk.number,
+        block_timestamp: block_timestamp,
+        events: events,
+    })
+}
+
+/// Keeps the sender and recipient of the Sablier streams created by or for the DAO accounts, keyed by
+/// contract_address:stream_id, as their withdrawals only name the recipient
+#[substreams::handlers::store]
+fn store_streams(
+    blk: eth::Block,
+    discovered_accounts: StoreGetProto<DiscoveredAccount>,
+    store: StoreSetString,
+) {
+    for trace in blk.transaction_traces.iter() {
+        for call in trace.calls.iter().filter(|c| c.state_reverted == false) {
+            for log in call.logs.iter() {
+                let event = match decode_stream_event(trace, log, 0) {
+                    Some(event) => event,
+                    None => continue,
+                };
+                if event.kind != "create" || event.protocol == "llamapay" {
+                    continue;
+                }
+                let dao_ids =
+                    transfer_dao_ids(&event.sender, &event.recipient, &discovered_accounts);
+                if dao_ids.is_empty() {
+                    continue;
+                }
+                store.set(
+                    log.ordinal,
+                    format!("{}:{}", event.contract_address, event.stream_id),
+                    &format!("{}:{}", event.sender, event.recipient),
+                );
+            }
+        }
+    }
+}
+
+/// Creations, withdrawals, cancellations and pauses of the Sablier V1, Sablier V2 LockupLinear and LlamaPay streams
+/// sent or received by the DAO accounts
+#[substreams::handlers::map]
+fn map_stream_events(
+    blk: eth::Block,
+    discovered_accounts: StoreGetProto<DiscoveredAccount>,
+    streams: StoreGetString,
+) -> Result<StreamEvents, substreams::errors::Error> {
+    let mut events: Vec<StreamEvent> = Vec::new();
+    // Streams created in this block, not yet in streams
+    let mut created: HashMap<String, String> = HashMap::new();
+
+    let block_timestamp: i64 = match blk.header {
+        Some(header) => header.timestamp.unwrap().seconds,
+        None => 0,
+    };
+
+    for trace in blk.transaction_traces.iter() {
+        for call in trace.calls.iter().filter(|c| c.state_reverted == false) {
+            for log in call.logs.iter() {
+                let mut event = match decode_stream_event(trace, log, block_timestamp as u64) {
+                    Some(event) => event,
+                    None => continue,
+                };
+
+                let key = format!("{}:{}", event.contract_address, event.stream_id);
+                if event.kind == "create" && event.protocol != "llamapay" {
+                    let parties = format!("{}:{}", event.sender, event.recipient);
+                    created.insert(key.to_string(), parties);
+                }
+                if event.sender.is_empty() {
+                    let parties = created
+                        .get(&key)
+                        .cloned()
+                        .or_else(|| streams.get_last(&key));
+                    if let Some((sender, recipient)) =
+                        parties.as_ref().and_then(|p| p.split_once(':'))
+                    {
+                        event.sender = sender.to_string();
+                        event.recipient = recipient.to_string();
+                    }
+                }
+
+                for dao_id in
+                    transfer_dao_ids(&event.sender, &event.recipient, &discovered_accounts)
+                {
+                    events.push(StreamEvent {
+                        dao_id: dao_id,
+                        tx_hash: Hex(&trace.hash).to_string(),
+                        log_index: log.block_index as u64,
+                        ordinal: log.ordinal,
+                        ..event.clone()
+                    });
+                }
+            }
+        }
+    }
+
+    events.sort_by_key(|e| e.ordinal);
+
+    Ok(StreamEvents {
+        block_number: blk.number,
+        block_timestamp: block_timestamp,
+        events: events,
+    })
+}
+
+/// Claims of the airdrops of the MerkleDistributor contracts listed in frens.yaml
+#[substreams::handlers::map]
+fn map_claims(blk: eth::Block) -> Result<Claims, substreams::errors::Error> {
+    let mut claims: Vec<Claim> = Vec::new();
+
+    for trace in blk.transaction_traces.iter() {
+        for call in trace.calls.iter().filter(|c| c.state_reverted == false) {
+            for log in call.logs.iter() {
+                for distributor in DISTRIBUTORS
+                    .iter()
+                    .filter(|d| d.distributor_address == &log.address[..20])
+                    .filter(|d| blk.number >= d.initial_block)
+                {
+                    let event = match MerkleDistributorClaimedEvent::match_and_decode(log) {
+                        Some(event) => event,
+                        None => continue,
+                    };
+                    // An index out of range is not a leaf of the merkle tree
+                    let claim_index = match checked_i64(&event.index) {
+                        Some(index) => index as u64,
+                        None => continue,
+                    };
+                    claims.push(Claim {
+                        dao_id: distributor.dao_id.to_string(),
+                        distributor_address: Hex(&log.address).to_string(),
+                        token_address: distributor.token.to_string(),
+                        tx_hash: Hex(&trace.hash).to_string(),
+                        log_index: log.block_index as u64,
+                        claim_index: claim_index,
+                        account: Hex(&event.account).to_string(),
+                        amount: event.amount.to_string(),
+                        ordinal: log.ordinal,
+                    });
+                }
+            }
+        }
+    }
+
+    let block_timestamp: i64 = match blk.header {
+        Some(header) => header.timestamp.unwrap().seconds,
+        None => 0,
+    };
+
+    claims.sort_by_key(|c| c.ordinal);
+
+    Ok(Claims {
+        block_number: blk.number,
+        block_timestamp: block_timestamp,
+        claims: claims,
+    })
+}
+
+/// Converts transfers to frenscan.types.v2, with bytes addresses and hashes, ordered by ordinal
+#[substreams::handlers::map]
+fn map_transfers_v2(transfers: Transfers) -> Result<v2::Transfers, substreams::errors::Error> {
+    let mut value_transfers: Vec<v2::ValueTransfer> = transfers
+        .value_transfers
+        .iter()
+        .map(|t| v2::ValueTransfer {
+            ordinal: t.ordinal,
+            hash: hex_to_bytes(&t.hash),
+            tx_index: t.tx_index,
+            call_index: t.call_index,
+            from: hex_to_bytes(&t.from),
+            to: hex_to_bytes(&t.to),
+            value: t.value.to_string(),
+            input: hex_to_bytes(&t.input),
+            reason: t.reason,
+            dao_id: t.dao_id.to_string(),
+            value_usd: t.value_usd.to_string(),
+        })
+        .collect();
+    let mut token_transfers: Vec<v2::TokenTransfer> = transfers
+        .token_transfers
+        .iter()
+        .map(token_transfer_v2)
+        .collect();
+    let mut issued_token_transfers: Vec<v2::TokenTransfer> = transfers
+        .issued_token_transfers
+        .iter()
+        .map(token_transfer_v2)
+        .collect();
+    let call_traces: Vec<v2::CallTraceRecord> = transfers
+        .call_traces
+        .iter()
+        .map(|c| v2::CallTraceRecord {
+            tx_hash: hex_to_bytes(&c.hash),
+            tx_index: c.index,
+            traces: c.traces.to_string(),
+            dao_id: c.dao_id.to_string(),
+        })
+        .collect();
+
+    value_transfers.sort_by_key(|t| t.ordinal);
+    token_transfers.sort_by_key(|t| t.ordinal);
+    issued_token_transfers.sort_by_key(|t| t.ordinal);
+
+    Ok(v2::Transfers {
+        block_number: transfers.block_number,
+        block_timestamp: transfers.block_timestamp,
+        value_transfers: value_transfers,
+        token_transfers: token_transfers,
+        call_traces: call_traces,
+        issued_token_transfers: issued_token_transfers,
+    })
+}
+
+#[substreams::handlers::map]
+pub fn db_out(
+    transfers: Transfers,
+    discovered_accounts: DiscoveredAccounts,
+    feed_prices: FeedPrices,
+    net_worths: NetWorths,
+    period_snapshots: PeriodSnapshots,
+    delegations: Delegations,
+    governance: GovernanceEvents,
+    timelock_operations: TimelockOperations,
+    vault_events: VaultEvents,
+    vault_prices: VaultPrices,
+    trades: Trades,
+    lending_events: LendingEvents,
+    lp_events: LpEvents,
+    stream_events: StreamEvents,
+    claims: Claims,
+) -> Result<DatabaseChanges, Error> {
+    let mut database_changes: DatabaseChanges = Default::default();
+
+    // Accounts must exist before their transfers are inserted
+    db::discovered_accounts_to_database_changes(&mut database_changes, discovered_accounts);
+    db::transfers_to_database_changes(&mut database_changes, transfers);
+    db::feed_prices_to_database_changes(&mut database_changes, feed_prices);
+    db::net_worths_to_database_changes(&mut database_changes, net_worths);
+    db::period_snapshots_to_database_changes(&mut database_changes, period_snapshots);
+    db::delegations_to_database_changes(&mut database_changes, delegations);
+    db::governance_to_database_changes(&mut database_changes, governance);
+    db::timelock_operations_to_database_changes(&mut database_changes, timelock_operations);
+    db::vault_events_to_database_changes(&mut database_changes, vault_events);
+    db::vault_prices_to_database_changes(&mut database_changes, vault_prices);
+    db::trades_to_database_changes(&mut database_changes, trades);
+    db::lending_events_to_database_changes(&mut database_changes, lending_events);
+    db::lp_events_to_database_changes(&mut database_changes, lp_events);
+    db::stream_events_to_database_changes(&mut database_changes, stream_events);
+    db::claims_to_database_changes(&mut database_changes, claims);
+
+    Ok(database_changes)
+}
+
+// DAOs which list an address as a treasury account or issued token in frens.yaml
+fn treasury_dao_ids(address: &String) -> Vec<String> {
+    TREASURY_ACCOUNT_DAOS
+        .iter()
+        .filter(|(_, a)| Hex(a).to_string() == *address)
+        .map(|(dao_id, _)| dao_id.to_string())
+        .collect()
+}
+
+// DAOs which list an address as an account in frens.yaml or discovered it
+fn account_dao_ids(
+    address: &String,
+    discovered_accounts: &StoreGetProto<DiscoveredAccount>,
+) -> Vec<String> {
+    let mut dao_ids = treasury_dao_ids(address);
+    dao_ids.extend(discovered_dao_ids(address, discovered_accounts));
+    dao_ids.sort();
+    dao_ids.dedup();
+    dao_ids
+}
+
+// DAOs which discovered an address, created by one of their treasury accounts or through one of their factories
+fn discovered_dao_ids(
+    address: &String,
+    discovered_accounts: &StoreGetProto<DiscoveredAccount>,
+) -> Vec<String> {
+    discovering_dao_ids()
+        .into_iter()
+        .filter(|dao_id| {
+            discovered_accounts
+                .get_last(format!("{}:{}", dao_id, address))
+                .is_some()
+        })
+        .collect()
+}
+
+// DAOs which may discover accounts: those with treasury accounts or factories in frens.yaml
+fn discovering_dao_ids() -> Vec<String> {
+    let mut dao_ids: Vec<String> = TREASURY_ACCOUNT_DAOS
+        .iter()
+        .map(|(dao_id, _)| dao_id.to_string())
+        .chain(FACTORIES.iter().map(|f| f.dao_id.to_string()))
+        .collect();
+    dao_ids.sort();
+    dao_ids.dedup();
+    dao_ids
+}
+
+// DAOs whose accounts, listed or discovered, are party to a transfer
+fn transfer_dao_ids(
+    from: &String,
+    to: &String,
+    discovered_accounts: &StoreGetProto<DiscoveredAccount>,
+) -> Vec<String> {
+    let mut dao_ids: Vec<String> = Vec::new();
+    for address in [from, to] {
+        dao_ids.extend(account_dao_ids(address, discovered_accounts));
+    }
+    dao_ids.sort();
+    dao_ids.dedup();
+    dao_ids
+}
+
+// (dao_id, address) of every account of the DAOs, listed in frens.yaml or discovered
+fn dao_account_list(
+    account_counts: &StoreGetInt64,
+    dao_accounts: &StoreGetProto<DiscoveredAccount>,
+) -> Vec<(String, String)> {
+    let mut accounts: Vec<(String, String)> = TREASURY_ACCOUNT_DAOS
+        .iter()
+        .map(|(dao_id, a)| (dao_id.to_string(), Hex(a).to_string()))
+        .collect();
+    for dao_id in discovering_dao_ids().iter() {
+        for index in 0..account_counts.get_last(dao_id).unwrap_or(0) {
+            if let Some(account) = dao_accounts.get_last(format!("{}:{}", dao_id, index)) {
+                accounts.push((dao_id.to_string(), account.address));
+            }
+        }
+    }
+    accounts
+}
+
+// USD value of an amount of a token at an ordinal of the block.  Empty when the token has no price
+fn value_usd(
+    token: &String,
+    value: &String,
+    ordinal: u64,
+    pool_prices: &StoreGetBigDecimal,
+    feed_prices: &StoreGetBigDecimal,
+) -> String {
+    let decimals = match token_decimals(token) {
+        Some(decimals) => decimals,
+        None => return "".to_string(),
+    };
+    let price = match usd_price(token, ordinal, pool_prices, feed_prices, 3) {
+        Some(price) => price,
+        None => return "".to_string(),
+    };
+
+    let amount = BigDecimal::from_str(value).unwrap() / exponent_to_big_decimal(decimals);
+    (amount * price).to_string()
+}
+
+// USD price of a token from its price feed, or through the price pools, following at most `hops`
+// pools to a token with a feed or a stable coin
+fn usd_price(
+    token: &String,
+    ordinal: u64,
+    pool_prices: &StoreGetBigDecimal,
+    feed_prices: &StoreGetBigDecimal,
+    hops: u32,
+) -> Option<BigDecimal> {
+    if let Some(price) = feed_price(token, ordinal, feed_prices) {
+        return Some(price);
+    }
+    if USD_TOKENS.iter().any(|t| *t == token) {
+        return Some(BigDecimal::one());
+    }
+    if hops == 0 {
+        return None;
+    }
+
+    for pool in PRICE_POOLS.iter() {
+        let (quote, inverse) = if pool.token0 == *token {
+            (&pool.token1, false)
+        } else if pool.token1 == *token {
+            (&pool.token0, true)
+        } else {
+            continue;
+        };
+        let price0 = match pool_prices.get_at(ordinal, Hex(&pool.pool_address).to_string()) {
+            Some(price0) if price0 != BigDecimal::zero() => price0,
+            _ => continue,
+        };
+        if let Some(quote_price) = usd_price(quote, ordinal, pool_prices, feed_prices, hops - 1) {
+            let price = if inverse {
+                BigDecimal::one() / price0
+            } else {
+                price0
+            };
+            return Some(price * quote_price);
+        }
+    }
+    None
+}
+
+// Last answer of the feed of a token.  ETH feeds also price WETH
+fn feed_price(
+    token: &String,
+    ordinal: u64,
+    feed_prices: &StoreGetBigDecimal,
+) -> Option<BigDecimal> {
+    PRICE_FEEDS
+        .iter()
+        .filter(|f| f.token == *token || (f.token == "ETH" && token == WETH_ADDRESS))
+        .find_map(|f| feed_prices.get_at(ordinal, &f.token))
+}
+
+// Decimals of a token, from the price feeds or the price pools it belongs to
+fn token_decimals(token: &String) -> Option<u32> {
+    let feed_decimals = PRICE_FEEDS
+        .iter()
+        .filter(|f| f.token == *token || (f.token == "ETH" && token == WETH_ADDRESS))
+        .find_map(|f| f.token_decimals);
+    if feed_decimals.is_some() {
+        return feed_decimals;
+    }
+
+    PRICE_POOLS.iter().find_map(|p| {
+        if p.token0 == *token {
+            Some(p.decimals0)
+        } else if p.token1 == *token {
+            Some(p.decimals1)
+        } else {
+            None
+        }
+    })
+}
+
+// Price of token0 in token1 from the sqrtPriceX96 of a Uniswap V3 pool, before adjusting for decimals
+fn sqrt_price_x96_to_price(sqrt_price_x96: &BigInt) -> BigDecimal {
+    let sqrt_price = BigDecimal::from(sqrt_price_x96.clone());
+    sqrt_price.clone() * sqrt_price / BigDecimal::from_str(Q192).unwrap()
+}
 
-    // check for block rewards
-    match new_value_transfer_block_rewards_from_block(&blk) {
-        Some(transfers) => {
-            value_transfers.extend(transfers);
+fn exponent_to_big_decimal(decimals: u32) -> BigDecimal {
+    BigDecimal::from_str(&format!("1{}", "0".repeat(decimals as usize))).unwrap()
+}
+
+// Signed change of the balance of a DAO account from a transfer
+struct BalanceDelta {
+    dao_id: String,
+    address: String,
+    token_address: String,
+    amount: BigInt,
+    fee: bool,
+    ordinal: u64,
+}
+
+// Balance changes of the DAO accounts party to the fungible token and ETH transfers of a block
+fn balance_deltas(
+    transfers: &Transfers,
+    discovered_accounts: &StoreGetProto<DiscoveredAccount>,
+) -> Vec<BalanceDelta> {
+    // (dao_id, from, to, token_address, value, fee, ordinal) of each fungible transfer
+    let token_transfers = transfers
+        .token_transfers
+        .iter()
+        .filter(|t| t.token_id.is_empty())
+        .map(|t| {
+            (
+                &t.dao_id,
+                &t.from,
+                &t.to,
+                t.token_address.as_str(),
+                &t.value,
+                false,
+                t.ordinal,
+            )
+        });
+    let value_transfers = transfers.value_transfers.iter().map(|t| {
+        let fee = t.reason == eth::balance_change::Reason::GasBuy as i32
+            || t.reason == eth::balance_change::Reason::GasRefund as i32;
+        (&t.dao_id, &t.from, &t.to, "ETH", &t.value, fee, t.ordinal)
+    });
+
+    let mut deltas: Vec<BalanceDelta> = Vec::new();
+    for (dao_id, from, to, token_address, value, fee, ordinal) in
+        token_transfers.chain(value_transfers)
+    {
+        let value = BigInt::from_str(value).unwrap();
+        for (address, amount) in [(to, value.clone()), (from, BigInt::zero() - value)] {
+            if is_dao_account(dao_id, address, discovered_accounts) {
+                deltas.push(BalanceDelta {
+                    dao_id: dao_id.to_string(),
+                    address: address.to_string(),
+                    token_address: token_address.to_string(),
+                    amount: amount,
+                    fee: fee,
+                    ordinal: ordinal,
+                });
+            }
         }
-        None => {}
     }
+    deltas
+}
 
-    for trace in blk.transaction_traces.iter() {
-        //Check for gas usage
-        let calls = &trace.calls;
-        let root_call = calls.into_iter().nth(0).unwrap();
-        if TREASURY_ADDRESSES
+// UTC day and month of a timestamp, with the first day of each as YYYY-MM-DD
+fn periods(timestamp: i64) -> [(&'static str, String); 2] {
+    let (year, month, day) = civil_from_days(timestamp / 86400);
+    [
+        ("day", format!("{:04}-{:02}-{:02}", year, month, day)),
+        ("month", format!("{:04}-{:02}-01", year, month)),
+    ]
+}
+
+// Gregorian date of a number of days since the Unix epoch: http://howardhinnant.github.io/date_algorithms.html
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = z / 146097;
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month as u32, day as u32)
+}
+
+// Check if an address is an account of a DAO, listed in frens.yaml or discovered
+fn is_dao_account(
+    dao_id: &String,
+    address: &String,
+    discovered_accounts: &StoreGetProto<DiscoveredAccount>,
+) -> bool {
+    treasury_dao_ids(address).contains(dao_id)
+        || discovered_accounts
+            .get_last(format!("{}:{}", dao_id, address))
+            .is_some()
+}
+
+// Tokens with a price source, whose balances are valued in the net worth snapshots
+fn priced_tokens() -> Vec<String> {
+    let mut tokens: Vec<String> = vec!["ETH".to_string()];
+    for pool in PRICE_POOLS.iter() {
+        tokens.push(pool.token0.to_string());
+        tokens.push(pool.token1.to_string());
+    }
+    tokens.extend(PRICE_FEEDS.iter().map(|f| f.token.to_string()));
+    tokens.extend(USD_TOKENS.iter().map(|t| t.to_string()));
+    tokens.sort();
+    tokens.dedup();
+    tokens
+}
+
+// Days since the Unix epoch of the block timestamp
+fn block_day(blk: &eth::Block) -> i64 {
+    match &blk.header {
+        Some(header) => header.timestamp.as_ref().unwrap().seconds / 86400,
+        None => 0,
+    }
+}
+
+// Decodes a call made by a timelock as an ERC-20 transfer(address,uint256).  A Compound Timelock may give the
+// function signature apart from the data, which then holds only the arguments
+fn decode_erc20_transfer(signature: &str, data: &Vec<u8>) -> Option<ERC20TransferFunction> {
+    let input: Vec<u8> = match signature {
+        "" => data.to_vec(),
+        "transfer(address,uint256)" => [&[0xa9, 0x05, 0x9c, 0xbb][..], data].concat(),
+        _ => return None,
+    };
+    ERC20TransferFunction::match_and_decode(&eth::Call {
+        input: input,
+        ..Default::default()
+    })
+}
+
+// (address, signed change of its shares) of each holder and the (numerator, denominator) of the share rate given
+// by a log of a rebasing token.  Aave emits the liquidity index with every event, Lido only on its oracle reports
+fn decode_share_events(
+    protocol: &str,
+    log: &eth::Log,
+) -> (Vec<(Vec<u8>, BigInt)>, Option<(BigInt, BigInt)>) {
+    let ray = BigInt::from_str(RAY).unwrap();
+    match protocol {
+        "lido" => {
+            if let Some(event) = LidoTransferSharesEvent::match_and_decode(log) {
+                let shares = event.shares_value;
+                (
+                    vec![
+                        (event.from, BigInt::zero() - shares.clone()),
+                        (event.to, shares),
+                    ],
+                    None,
+                )
+            } else if let Some(event) = LidoTokenRebasedEvent::match_and_decode(log) {
+                (
+                    vec![],
+                    Some((event.post_total_ether, event.post_total_shares)),
+                )
+            } else {
+                (vec![], None)
+            }
+        }
+        // The events of V2 give underlying amounts, which the aToken scales by the index
+        "aave_v2" => {
+            if let Some(event) = AaveV2MintEvent::match_and_decode(log) {
+                let shares = ray_div(event.value, &event.index);
+                (vec![(event.from, shares)], Some((event.index, ray)))
+            } else if let Some(event) = AaveV2BurnEvent::match_and_decode(log) {
+                let shares = ray_div(event.value, &event.index);
+                (
+                    vec![(event.from, BigInt::zero() - shares)],
+                    Some((event.index, ray)),
+                )
+            } else if let Some(event) = AaveV2BalanceTransferEvent::match_and_decode(log) {
+                let shares = ray_div(event.value, &event.index);
+                (
+                    vec![
+                        (event.from, BigInt::zero() - shares.clone()),
+                        (event.to, shares),
+                    ],
+                    Some((event.index, ray)),
+                )
+            } else {
+                (vec![], None)
+            }
+        }
+        // V3 adds the interest accrued since the last action of the holder to the amounts of Mint and Burn, and
+        // gives BalanceTransfer already scaled
+        _ => {
+            if let Some(event) = AaveV3MintEvent::match_and_decode(log) {
+                let shares = ray_div(event.value - event.balance_increase, &event.index);
+                (vec![(event.on_behalf_of, shares)], Some((event.index, ray)))
+            } else if let Some(event) = AaveV3BurnEvent::match_and_decode(log) {
+                let shares = ray_div(event.value + event.balance_increase, &event.index);
+                (
+                    vec![(event.from, BigInt::zero() - shares)],
+                    Some((event.index, ray)),
+                )
+            } else if let Some(event) = AaveV3BalanceTransferEvent::match_and_decode(log) {
+                (
+                    vec![
+                        (event.from, BigInt::zero() - event.value.clone()),
+                        (event.to, event.value),
+                    ],
+                    Some((event.index, ray)),
+                )
+            } else {
+                (vec![], None)
+            }
+        }
+    }
+}
+
+// Aave's rayDiv, rounding half up the magnitude of a signed amount
+fn ray_div(amount: BigInt, index: &BigInt) -> BigInt {
+    if amount < BigInt::zero() {
+        return BigInt::zero() - ray_div(BigInt::zero() - amount, index);
+    }
+    (amount * BigInt::from_str(RAY).unwrap() + index.clone() / BigInt::from(2)) / index.clone()
+}
+
+// Balance worth an amount of shares.  Lido rounds down, Aave's rayMul rounds half up
+fn share_balance(shares: &BigInt, rate: &ShareRate, protocol: &str) -> BigInt {
+    let numerator = BigInt::from_str(&rate.numerator).unwrap();
+    let denominator = BigInt::from_str(&rate.denominator).unwrap();
+    let half = if protocol == "lido" {
+        BigInt::zero()
+    } else {
+        denominator.clone() / BigInt::from(2)
+    };
+    (shares.clone() * numerator + half) / denominator
+}
+
+// Changes of the balances of the DAO accounts in the rebasing tokens that their Transfer events leave out.  At the
+// end of each transaction that changes the shares of an account or the rate of a token, the balance worth the
+// shares is compared with the balance before it plus the transfers of the transaction.  Balances are only known
+// once a rate of the token has been seen
+fn rebase_transfers(
+    blk: &eth::Block,
+    share_changes: &ShareChanges,
+    shares: &StoreGetBigInt,
+    share_rates: &StoreGetProto<ShareRate>,
+    accounts: &Vec<(String, String)>,
+    token_transfers: &Vec<TokenTransfer>,
+) -> Vec<TokenTransfer> {
+    let mut rebases: Vec<TokenTransfer> = Vec::new();
+
+    for token in REBASING_TOKENS.iter() {
+        let token_address = Hex(&token.token_address).to_string();
+        let rates: Vec<&ShareRate> = share_changes
+            .share_rates
             .iter()
-            .any(|a| a.to_vec() == root_call.caller)
-        {
-            match new_gas_transfer_from_call(&trace, &root_call) {
-                Some(transfer) => {
-                    value_transfers.extend(transfer);
+            .filter(|r| r.token_address == token_address)
+            .collect();
+        let changes: Vec<&ShareChange> = share_changes
+            .share_changes
+            .iter()
+            .filter(|c| c.token_address == token_address)
+            .collect();
+
+        // A new rate changes the balance of every holder
+        let mut holders: BTreeSet<(String, String)> = changes
+            .iter()
+            .map(|c| (c.dao_id.to_string(), c.address.to_string()))
+            .collect();
+        if !rates.is_empty() {
+            holders.extend(accounts.iter().cloned());
+        }
+
+        for (dao_id, address) in holders.iter() {
+            let key = format!("{}:{}:{}", dao_id, address, token_address);
+            // (log_index, ordinal) of the events changing the balance of the account
+            let events: Vec<(u64, u64)> = rates
+                .iter()
+                .map(|r| (r.log_index, r.ordinal))
+                .chain(
+                    changes
+                        .iter()
+                        .filter(|c| c.dao_id == *dao_id && c.address == *address)
+                        .map(|c| (c.log_index, c.ordinal)),
+                )
+                .collect();
+            let balance_at = |ordinal: u64| -> Option<BigInt> {
+                let rate = share_rates.get_at(ordinal, &token_address)?;
+                let held = shares.get_at(ordinal, &key).unwrap_or(BigInt::zero());
+                Some(share_balance(&held, &rate, &token.protocol))
+            };
+
+            let mut previous = balance_at(0);
+            for trace in blk.transaction_traces.iter() {
+                let in_trace =
+                    |ordinal: u64| ordinal >= trace.begin_ordinal && ordinal <= trace.end_ordinal;
+                let log_index = match events
+                    .iter()
+                    .filter(|(_, ordinal)| in_trace(*ordinal))
+                    .max_by_key(|(_, ordinal)| *ordinal)
+                {
+                    Some((log_index, _)) => *log_index,
+                    None => continue,
+                };
+
+                let balance = balance_at(trace.end_ordinal);
+                if let (Some(before), Some(after)) = (&previous, &balance) {
+                    let mut change = after.clone() - before.clone();
+                    for t in token_transfers
+                        .iter()
+                        .filter(|t| t.dao_id == *dao_id && t.token_address == token_address)
+                        .filter(|t| in_trace(t.ordinal))
+                    {
+                        let value = BigInt::from_str(&t.value).unwrap();
+                        if t.to == *address {
+                            change = change - value.clone();
+                        }
+                        if t.from == *address {
+                            change = change + value;
+                        }
+                    }
+
+                    if change != BigInt::zero() {
+                        let (from, to) = if change > BigInt::zero() {
+                            (Hex(NULL_ADDRESS).to_string(), address.to_string())
+                        } else {
+                            (address.to_string(), Hex(NULL_ADDRESS).to_string())
+                        };
+                        rebases.push(TokenTransfer {
+                            tx_hash: Hex(&trace.hash).to_string(),
+                            call_index: 0,
+                            log_index: log_index,
+                            from: from,
+                            to: to,
+                            value: unsigned_decimal(&change),
+                            token_address: token_address.to_string(),
+                            token_id: "".to_string(),
+                            dao_id: dao_id.to_string(),
+                            ordinal: trace.end_ordinal,
+                            value_usd: "".to_string(),
+                            reason: "rebase".to_string(),
+                        });
+                    }
                 }
-                None => {}
+                previous = balance;
             }
         }
+    }
 
-        // Check calls
-        for call in trace
+    rebases
+}
+
+// Token of an ERC-20 transfer of an amount from or to an address in a transaction, eg: the asset of a vault or a
+// token of a pool.  Empty when there is no such transfer, eg: when the contract moves the tokens through another one
+fn transferred_token(
+    trace: &eth::TransactionTrace,
+    from: Option<&String>,
+    to: Option<&String>,
+    amount: &BigInt,
+) -> String {
+    trace
+        .calls
+        .iter()
+        .filter(|c| c.state_reverted == false)
+        .flat_map(|c| c.logs.iter())
+        .filter_map(|log| ERC20TransferEvent::match_and_decode(log).map(|event| (log, event)))
+        .filter(|(_, event)| event.value == *amount)
+        .filter(|(_, event)| from.map_or(true, |a| Hex(&event.from).to_string() == *a))
+        .filter(|(_, event)| to.map_or(true, |a| Hex(&event.to).to_string() == *a))
+        .map(|(log, _)| Hex(&log.address).to_string())
+        .next()
+        .unwrap_or_default()
+}
+
+// (dao_id, account) of the DAO accounts behind a swap: its recipient, its sender, an account which called its
+// sender, such as a router, or the sender of the transaction.  The first account found is kept for each DAO
+fn trade_accounts(
+    trace: &eth::TransactionTrace,
+    recipient: &String,
+    sender: &String,
+    discovered_accounts: &StoreGetProto<DiscoveredAccount>,
+) -> Vec<(String, String)> {
+    let mut candidates: Vec<String> = vec![recipient.to_string(), sender.to_string()];
+    candidates.extend(
+        trace
             .calls
             .iter()
-            .filter(|c| c.call_type != eth::CallType::Delegate as i32)
             .filter(|c| c.state_reverted == false)
-        {
-            // Check calls for value transfers
-            if TREASURY_ADDRESSES
-                .iter()
-                .any(|a| a.to_vec() == call.caller || a.to_vec() == call.address)
-            {
-                match new_value_transfer_from_call(&trace, &call) {
-                    Some(transfer) => {
-                        value_transfers.push(transfer);
-                    }
-                    None => {}
-                }
+            .filter(|c| Hex(&c.address).to_string() == *sender)
+            .map(|c| Hex(&c.caller).to_string()),
+    );
+    candidates.push(Hex(&trace.from).to_string());
+
+    let mut accounts: Vec<(String, String)> = Vec::new();
+    for address in candidates.iter().filter(|a| !a.is_empty()) {
+        for dao_id in account_dao_ids(address, discovered_accounts) {
+            if !accounts.iter().any(|(d, _)| *d == dao_id) {
+                accounts.push((dao_id, address.to_string()));
             }
+        }
+    }
+    accounts
+}
 
-            // Check logs for token transfers
-            token_transfers.extend(get_erc_transfers_from_logs(
-                &call.logs,
-                &trace.hash,
-                call.index,
-            ));
+// Bought tokens per sold token of a trade, adjusted for their decimals.  Empty when either has unknown decimals
+fn trade_price(trade: &TradeRecord) -> String {
+    let (sold_decimals, bought_decimals) = match (
+        token_decimals(&trade.sold_token),
+        token_decimals(&trade.bought_token),
+    ) {
+        (Some(sold_decimals), Some(bought_decimals)) => (sold_decimals, bought_decimals),
+        _ => return "".to_string(),
+    };
+    let sold = BigDecimal::from_str(&trade.sold_amount).unwrap();
+    if sold == BigDecimal::zero() {
+        return "".to_string();
+    }
 
-            // Check logs for issued token transfers
-            issued_transfers.extend(get_erc_issued_transfers_from_logs(
-                &call.logs,
-                &trace.hash,
-                call.index,
+    let bought = BigDecimal::from_str(&trade.bought_amount).unwrap()
+        / exponent_to_big_decimal(bought_decimals);
+    (bought / (sold / exponent_to_big_decimal(sold_decimals))).to_string()
+}
+
+// Position changes of an Aave pool event.  Withdraw and LiquidationCall are the same in V2 and V3
+fn aave_lending_events(protocol: &str, log: &eth::Log) -> Vec<LendingEvent> {
+    let market = LendingEvent {
+        protocol: protocol.to_string(),
+        market_address: Hex(&log.address).to_string(),
+        ..Default::default()
+    };
+    let change = |kind: &str,
+                  side: &str,
+                  account: &[u8],
+                  counterparty: &[u8],
+                  asset: &[u8],
+                  amount: &BigInt| {
+        LendingEvent {
+            account: Hex(account).to_string(),
+            counterparty: Hex(counterparty).to_string(),
+            asset_address: Hex(asset).to_string(),
+            kind: kind.to_string(),
+            side: side.to_string(),
+            amount: amount.to_string(),
+            ..market.clone()
+        }
+    };
+
+    if let Some(e) = AaveV2DepositEvent::match_and_decode(log) {
+        vec![change(
+            "supply",
+            "collateral",
+            &e.on_behalf_of,
+            &e.user,
+            &e.reserve,
+            &e.amount,
+        )]
+    } else if let Some(e) = AaveV3SupplyEvent::match_and_decode(log) {
+        vec![change(
+            "supply",
+            "collateral",
+            &e.on_behalf_of,
+            &e.user,
+            &e.reserve,
+            &e.amount,
+        )]
+    } else if let Some(e) = AaveWithdrawEvent::match_and_decode(log) {
+        vec![change(
+            "withdraw",
+            "collateral",
+            &e.user,
+            &e.to,
+            &e.reserve,
+            &e.amount,
+        )]
+    } else if let Some(e) = AaveV2BorrowEvent::match_and_decode(log) {
+        vec![change(
+            "borrow",
+            "debt",
+            &e.on_behalf_of,
+            &e.user,
+            &e.reserve,
+            &e.amount,
+        )]
+    } else if let Some(e) = AaveV3BorrowEvent::match_and_decode(log) {
+        vec![change(
+            "borrow",
+            "debt",
+            &e.on_behalf_of,
+            &e.user,
+            &e.reserve,
+            &e.amount,
+        )]
+    } else if let Some(e) = AaveV2RepayEvent::match_and_decode(log) {
+        vec![change(
+            "repay", "debt", &e.user, &e.repayer, &e.reserve, &e.amount,
+        )]
+    } else if let Some(e) = AaveV3RepayEvent::match_and_decode(log) {
+        let mut changes = vec![change(
+            "repay", "debt", &e.user, &e.repayer, &e.reserve, &e.amount,
+        )];
+        // Repaying with aTokens burns collateral of the same asset
+        if e.use_a_tokens {
+            changes.push(change(
+                "repay",
+                "collateral",
+                &e.user,
+                &e.repayer,
+                &e.reserve,
+                &e.amount,
             ));
         }
+        changes
+    } else if let Some(e) = AaveLiquidationCallEvent::match_and_decode(log) {
+        vec![
+            change(
+                "liquidation",
+                "debt",
+                &e.user,
+                &e.liquidator,
+                &e.debt_asset,
+                &e.debt_to_cover,
+            ),
+            change(
+                "liquidation",
+                "collateral",
+                &e.user,
+                &e.liquidator,
+                &e.collateral_asset,
+                &e.liquidated_collateral_amount,
+            ),
+        ]
+    } else {
+        vec![]
     }
+}
 
-    // Save call traces for transactions with transfers
-    let mut tx_hashes: Vec<&String> = token_transfers.iter().map(|t| &t.tx_hash).collect();
-    tx_hashes.extend(issued_transfers.iter().map(|t| &t.tx_hash));
-    tx_hashes.extend(
-        value_transfers
-            .iter()
-            .filter(|t| {
-                let r = eth::balance_change::Reason::from_i32(t.reason).unwrap();
-                r != eth::balance_change::Reason::RewardMineBlock
-                    && r != eth::balance_change::Reason::RewardMineUncle
-            })
-            .map(|v| &v.hash),
-    );
-    tx_hashes.sort();
-    tx_hashes.dedup();
+// Position changes of a Compound cToken event.  The asset is the token transferred to or from the cToken in the
+// transaction, and events whose asset can not be found are skipped
+fn compound_lending_events(trace: &eth::TransactionTrace, log: &eth::Log) -> Vec<LendingEvent> {
+    let market_address = Hex(&log.address).to_string();
+    let market = LendingEvent {
+        protocol: "compound".to_string(),
+        market_address: market_address.to_string(),
+        ..Default::default()
+    };
+    let asset = |from: Option<&String>, to: Option<&String>, amount: &BigInt| {
+        if market_address == CETH_ADDRESS {
+            "ETH".to_string()
+        } else {
+            transferred_token(trace, from, to, amount)
+        }
+    };
 
-    for trace in blk.transaction_traces.iter() {
-        if tx_hashes
-            .iter()
-            .any(|&h| *h == Hex(&trace.hash).to_string())
-        {
-            call_traces.push(get_call_traces(trace));
+    let event = if let Some(event) = CompoundMintEvent::match_and_decode(log) {
+        LendingEvent {
+            account: Hex(&event.minter).to_string(),
+            asset_address: asset(None, Some(&market_address), &event.mint_amount),
+            kind: "supply".to_string(),
+            side: "collateral".to_string(),
+            amount: event.mint_amount.to_string(),
+            tokens: event.mint_tokens.to_string(),
+            ..market
+        }
+    } else if let Some(event) = CompoundRedeemEvent::match_and_decode(log) {
+        LendingEvent {
+            account: Hex(&event.redeemer).to_string(),
+            asset_address: asset(Some(&market_address), None, &event.redeem_amount),
+            kind: "withdraw".to_string(),
+            side: "collateral".to_string(),
+            amount: event.redeem_amount.to_string(),
+            tokens: event.redeem_tokens.to_string(),
+            ..market
         }
+    } else if let Some(event) = CompoundBorrowEvent::match_and_decode(log) {
+        LendingEvent {
+            account: Hex(&event.borrower).to_string(),
+            asset_address: asset(Some(&market_address), None, &event.borrow_amount),
+            kind: "borrow".to_string(),
+            side: "debt".to_string(),
+            amount: event.borrow_amount.to_string(),
+            account_borrows: event.account_borrows.to_string(),
+            ..market
+        }
+    } else if let Some(event) = CompoundRepayBorrowEvent::match_and_decode(log) {
+        LendingEvent {
+            account: Hex(&event.borrower).to_string(),
+            counterparty: Hex(&event.payer).to_string(),
+            asset_address: asset(None, Some(&market_address), &event.repay_amount),
+            kind: "repay".to_string(),
+            side: "debt".to_string(),
+            amount: event.repay_amount.to_string(),
+            account_borrows: event.account_borrows.to_string(),
+            ..market
+        }
+    } else if let Some(event) = CompoundLiquidateBorrowEvent::match_and_decode(log) {
+        // The debt repaid has its own RepayBorrow event.  The collateral is seized in cTokens of another market
+        return vec![LendingEvent {
+            account: Hex(&event.borrower).to_string(),
+            counterparty: Hex(&event.liquidator).to_string(),
+            market_address: Hex(&event.c_token_collateral).to_string(),
+            kind: "liquidation".to_string(),
+            side: "collateral".to_string(),
+            tokens: event.seize_tokens.to_string(),
+            ..market
+        }];
+    } else {
+        return vec![];
+    };
+
+    if event.asset_address.is_empty() {
+        return vec![];
     }
+    vec![event]
+}
 
-    let block_timestamp: i64 = match blk.header {
-        Some(header) => header.timestamp.unwrap().seconds,
-        None => 0,
+// Pool and ticks of a position manager event, from the last Mint, Burn or Collect of a pool position owned by the
+// position manager before it
+fn lp_position_range(logs: &[&eth::Log], kind: &str) -> Option<(String, i32, i32)> {
+    for log in logs.iter().rev() {
+        let range = match kind {
+            "increase" => UniswapV3MintEvent::match_and_decode(log)
+                .map(|e| (e.owner, e.tick_lower, e.tick_upper)),
+            "decrease" => UniswapV3BurnEvent::match_and_decode(log)
+                .map(|e| (e.owner, e.tick_lower, e.tick_upper)),
+            _ => UniswapV3CollectEvent::match_and_decode(log)
+                .map(|e| (e.owner, e.tick_lower, e.tick_upper)),
+        };
+        if let Some((owner, tick_lower, tick_upper)) = range {
+            if Hex(&owner).to_string() == UNISWAP_V3_POSITION_MANAGER {
+                return Some((
+                    Hex(&log.address).to_string(),
+                    tick_lower.to_i32(),
+                    tick_upper.to_i32(),
+                ));
+            }
+        }
+    }
+    None
+}
+
+// Token of a position manager event, transferred to the pool by an increase, or from the pool by a collect.  Empty
+// for a decrease, which leaves the tokens in the pool until collected, or when the amount is zero
+fn lp_event_token(
+    trace: &eth::TransactionTrace,
+    lp_event: &LpEvent,
+    pool_address: &String,
+    amount: &String,
+) -> String {
+    let amount = BigInt::from_str(amount).unwrap();
+    if amount == BigInt::zero() {
+        return "".to_string();
+    }
+    match lp_event.kind.as_str() {
+        "increase" => transferred_token(trace, None, Some(pool_address), &amount),
+        "collect" => transferred_token(trace, Some(pool_address), None, &amount),
+        _ => "".to_string(),
+    }
+}
+
+// Stream event of a Sablier V1, Sablier V2 LockupLinear or LlamaPay log, without its DAO and position in the block
+fn decode_stream_event(
+    trace: &eth::TransactionTrace,
+    log: &eth::Log,
+    block_timestamp: u64,
+) -> Option<StreamEvent> {
+    let contract_address = Hex(&log.address).to_string();
+    let protocol = stream_protocol(&contract_address)?;
+    let stream = |protocol: &str, stream_id: String, kind: &str| StreamEvent {
+        protocol: protocol.to_string(),
+        contract_address: contract_address.to_string(),
+        stream_id: stream_id,
+        kind: kind.to_string(),
+        ..Default::default()
     };
 
-    token_transfers.sort_by(|a, b| a.log_index.cmp(&b.log_index));
-    value_transfers.sort_unstable_by_key(|x| (x.tx_index, x.call_index));
+    match protocol.as_str() {
+        "sablier_v1" => {
+            if let Some(event) = SablierV1CreateStreamEvent::match_and_decode(log) {
+                return Some(StreamEvent {
+                    sender: Hex(&event.sender).to_string(),
+                    recipient: Hex(&event.recipient).to_string(),
+                    token_address: Hex(&event.token_address).to_string(),
+                    amount_per_sec: stream_rate(
+                        &event.deposit,
+                        &event.start_time,
+                        &event.stop_time,
+                    ),
+                    deposit: event.deposit.to_string(),
+                    start_time: checked_i64(&event.start_time)? as u64,
+                    stop_time: checked_i64(&event.stop_time)? as u64,
+                    ..stream("sablier_v1", event.stream_id.to_string(), "create")
+                });
+            }
+            if let Some(event) = SablierV1WithdrawFromStreamEvent::match_and_decode(log) {
+                let recipient = Hex(&event.recipient).to_string();
+                return Some(StreamEvent {
+                    token_address: transferred_token(
+                        trace,
+                        Some(&contract_address),
+                        Some(&recipient),
+                        &event.amount,
+                    ),
+                    recipient: recipient,
+                    amount: event.amount.to_string(),
+                    ..stream("sablier_v1", event.stream_id.to_string(), "withdraw")
+                });
+            }
+            if let Some(event) = SablierV1CancelStreamEvent::match_and_decode(log) {
+                let sender = Hex(&event.sender).to_string();
+                let recipient = Hex(&event.recipient).to_string();
+                // Sablier V1 pays both balances on cancel
+                let token_address = if event.recipient_balance > BigInt::zero() {
+                    transferred_token(
+                        trace,
+                        Some(&contract_address),
+                        Some(&recipient),
+                        &event.recipient_balance,
+                    )
+                } else {
+                    transferred_token(
+                        trace,
+                        Some(&contract_address),
+                        Some(&sender),
+                        &event.sender_balance,
+                    )
+                };
+                return Some(StreamEvent {
+                    sender: sender,
+                    recipient: recipient,
+                    token_address: token_address,
+                    amount: event.recipient_balance.to_string(),
+                    refund: event.sender_balance.to_string(),
+                    ..stream("sablier_v1", event.stream_id.to_string(), "cancel")
+                });
+            }
+        }
+        "sablier_v2" => {
+            if let Some(event) = SablierV2CreateLockupLinearStreamEvent::match_and_decode(log) {
+                let (deposit, _, _) = event.amounts;
+                let (start_time, cliff_time, stop_time) = event.range;
+                return Some(StreamEvent {
+                    sender: Hex(&event.sender).to_string(),
+                    recipient: Hex(&event.recipient).to_string(),
+                    token_address: Hex(&event.asset).to_string(),
+                    amount_per_sec: stream_rate(&deposit, &start_time, &stop_time),
+                    deposit: deposit.to_string(),
+                    start_time: checked_i64(&start_time)? as u64,
+                    cliff_time: checked_i64(&cliff_time)? as u64,
+                    stop_time: checked_i64(&stop_time)? as u64,
+                    ..stream("sablier_v2", event.stream_id.to_string(), "create")
+                });
+            }
+            if let Some(event) = SablierV2WithdrawFromLockupStreamEvent::match_and_decode(log) {
+                return Some(StreamEvent {
+                    recipient: Hex(&event.to).to_string(),
+                    token_address: Hex(&event.asset).to_string(),
+                    amount: event.amount.to_string(),
+                    ..stream("sablier_v2", event.stream_id.to_string(), "withdraw")
+                });
+            }
+            if let Some(event) = SablierV2CancelLockupStreamEvent::match_and_decode(log) {
+                // The recipient amount stays in the stream until withdrawn
+                return Some(StreamEvent {
+                    sender: Hex(&event.sender).to_string(),
+                    recipient: Hex(&event.recipient).to_string(),
+                    token_address: Hex(&event.asset).to_string(),
+                    amount: event.recipient_amount.to_string(),
+                    refund: event.sender_amount.to_string(),
+                    ..stream("sablier_v2", event.stream_id.to_string(), "cancel")
+                });
+            }
+        }
+        "llamapay" => {
+            // LlamaPay streams have no end, and are paused or cancelled by the payer
+            let llamapay = if let Some(e) = LlamaPayStreamCreatedEvent::match_and_decode(log) {
+                Some(("create", e.from, e.to, e.amount_per_sec, e.stream_id))
+            } else if let Some(e) = LlamaPayStreamCreatedWithReasonEvent::match_and_decode(log) {
+                Some(("create", e.from, e.to, e.amount_per_sec, e.stream_id))
+            } else if let Some(e) = LlamaPayStreamCancelledEvent::match_and_decode(log) {
+                Some(("cancel", e.from, e.to, e.amount_per_sec, e.stream_id))
+            } else if let Some(e) = LlamaPayStreamPausedEvent::match_and_decode(log) {
+                Some(("pause", e.from, e.to, e.amount_per_sec, e.stream_id))
+            } else {
+                None
+            };
+            if let Some((kind, from, to, amount_per_sec, stream_id)) = llamapay {
+                let (start_time, stop_time) = match kind {
+                    "create" => (block_timestamp, 0),
+                    _ => (0, block_timestamp),
+                };
+                return Some(StreamEvent {
+                    sender: Hex(&from).to_string(),
+                    recipient: Hex(&to).to_string(),
+                    amount_per_sec: amount_per_sec.to_string(),
+                    start_time: start_time,
+                    stop_time: stop_time,
+                    ..stream("llamapay", Hex(&stream_id).to_string(), kind)
+                });
+            }
+            if let Some(event) = LlamaPayWithdrawEvent::match_and_decode(log) {
+                let recipient = Hex(&event.to).to_string();
+                return Some(StreamEvent {
+                    sender: Hex(&event.from).to_string(),
+                    token_address: paid_token(trace, &contract_address, &recipient),
+                    recipient: recipient,
+                    amount_per_sec: event.amount_per_sec.to_string(),
+                    amount: event.amount.to_string(),
+                    ..stream("llamapay", Hex(&event.stream_id).to_string(), "withdraw")
+                });
+            }
+        }
+        _ => {}
+    }
 
-    Ok(Transfers {
-        block_number: blk.number,
-        block_timestamp: block_timestamp,
-        value_transfers: value_transfers,
-        token_transfers: token_transfers,
-        issued_token_transfers: issued_transfers,
-        call_traces: call_traces,
-    })
+    None
 }
 
-#[substreams::handlers::map]
-pub fn db_out(transfers: Transfers) -> Result<DatabaseChanges, Error> {
-    let mut database_changes: DatabaseChanges = Default::default();
+// Protocol of a stream contract: the built in Sablier deployments, then the streams section of frens.yaml
+fn stream_protocol(contract_address: &String) -> Option<String> {
+    if SABLIER_V1.contains(&contract_address.as_str()) {
+        return Some("sablier_v1".to_string());
+    }
+    if contract_address == SABLIER_V2_LOCKUP_LINEAR {
+        return Some("sablier_v2".to_string());
+    }
+    STREAM_CONTRACTS
+        .iter()
+        .find(|c| Hex(&c.contract_address).to_string() == *contract_address)
+        .and_then(|c| c.protocol.clone())
+}
 
-    db::transfers_to_database_changes(&mut database_changes, transfers);
+// Tokens per second of a stream releasing its deposit linearly from start to stop
+fn stream_rate(deposit: &BigInt, start_time: &BigInt, stop_time: &BigInt) -> String {
+    let duration = stop_time.clone() - start_time.clone();
+    if duration <= BigInt::zero() {
+        return "".to_string();
+    }
+    (BigDecimal::from(deposit.clone()) / BigDecimal::from(duration)).to_string()
+}
 
-    Ok(database_changes)
+// Token of the first ERC20 transfer between two addresses in a transaction, whatever its amount
+fn paid_token(trace: &eth::TransactionTrace, from: &String, to: &String) -> String {
+    trace
+        .calls
+        .iter()
+        .filter(|c| c.state_reverted == false)
+        .flat_map(|c| c.logs.iter())
+        .filter_map(|log| ERC20TransferEvent::match_and_decode(log).map(|event| (log, event)))
+        .find(|(_, event)| {
+            Hex(&event.from).to_string() == *from && Hex(&event.to).to_string() == *to
+        })
+        .map(|(log, _)| Hex(&log.address).to_string())
+        .unwrap_or_default()
+}
+
+fn transfer_to(transfer: &Option<ERC20TransferFunction>) -> String {
+    match transfer {
+        Some(t) => Hex(&t.to).to_string(),
+        None => "".to_string(),
+    }
+}
+
+fn transfer_amount(transfer: &Option<ERC20TransferFunction>) -> String {
+    match transfer {
+        Some(t) => t.value.to_string(),
+        None => "".to_string(),
+    }
+}
+
+// Check if an address is a treasury account from frens.yaml, or one discovered through a factory
+fn is_treasury_address(
+    address: &[u8],
+    discovered_accounts: &StoreGetProto<DiscoveredAccount>,
+) -> bool {
+    if TREASURY_ADDRESSES.iter().any(|&a| a == address) {
+        return true;
+    }
+    !discovered_dao_ids(&Hex(address).to_string(), discovered_accounts).is_empty()
+}
+
+// Contracts deployed in a call by an account of some DAOs, which join the DAOs of their creator, and proxies
+// registered through a factory
+fn call_creations(
+    block_number: u64,
+    trace: &eth::TransactionTrace,
+    call: &eth::Call,
+    caller_dao_ids: &Vec<String>,
+) -> Vec<DiscoveredAccount> {
+    let mut accounts: Vec<DiscoveredAccount> = Vec::new();
+
+    if call.call_type == eth::CallType::Create as i32 {
+        for dao_id in caller_dao_ids.iter() {
+            accounts.push(DiscoveredAccount {
+                address: Hex(&call.address).to_string(),
+                creator: Hex(&call.caller).to_string(),
+                factory: "".to_string(),
+                tx_hash: Hex(&trace.hash).to_string(),
+                block_number: block_number,
+                dao_id: dao_id.to_string(),
+            });
+        }
+    }
+
+    for factory in FACTORIES
+        .iter()
+        .filter(|f| f.factory_address.to_vec() == call.address)
+        .filter(|f| block_number >= f.initial_block)
+        .filter(|f| !f.treasury_only.unwrap_or(true) || caller_dao_ids.contains(&f.dao_id))
+    {
+        for log in call.logs.iter() {
+            if let Some(event) = SafeProxyCreationEvent::match_and_decode(log) {
+                accounts.push(DiscoveredAccount {
+                    address: Hex(&event.proxy).to_string(),
+                    creator: Hex(&factory.factory_address).to_string(),
+                    factory: factory.name.to_string(),
+                    tx_hash: Hex(&trace.hash).to_string(),
+                    block_number: block_number,
+                    dao_id: factory.dao_id.to_string(),
+                });
+            }
+        }
+    }
+
+    accounts
+}
+
+// One creation per DAO and address, leaving out the accounts listed in frens.yaml, which are tracked from their
+// own initial_block
+fn unique_creations(accounts: Vec<DiscoveredAccount>) -> Vec<DiscoveredAccount> {
+    let mut accounts = accounts;
+    accounts.retain(|a| !treasury_dao_ids(&a.address).contains(&a.dao_id));
+    // An account may be discovered by several DAOs, whose treasury accounts are shared
+    accounts.sort_by(|a, b| (&a.dao_id, &a.address).cmp(&(&b.dao_id, &b.address)));
+    accounts.dedup_by(|a, b| a.dao_id == b.dao_id && a.address == b.address);
+    accounts
 }
 
 fn get_erc_transfers_from_logs<'a>(
     logs: &'a Vec<eth::Log>,
     hash: &'a Vec<u8>,
     call_index: u32,
+    discovered_accounts: &'a StoreGetProto<DiscoveredAccount>,
 ) -> impl Iterator<Item = TokenTransfer> + 'a {
+    let is_treasury_transfer = move |from: &Vec<u8>, to: &Vec<u8>| {
+        from != to
+            && (is_treasury_address(&to[..20], discovered_accounts)
+                || is_treasury_address(&from[..20], discovered_accounts))
+    };
+
     logs.iter().flat_map(move |log| {
         if let Some(event) = ERC20TransferEvent::match_and_decode(log) {
-            if TREASURY_ADDRESSES.iter().any(|&a| {
-                (a == &event.to[..20] || a == &event.from[..20]) && (&event.to != &event.from)
-            }) {
+            if is_treasury_transfer(&event.from, &event.to) {
                 return vec![new_erc20_transfer(&hash, call_index, log, event)];
             }
         }
 
         if let Some(event) = ERC721TransferEvent::match_and_decode(log) {
-            if TREASURY_ADDRESSES.iter().any(|&a| {
-                (a == &event.to[..20] || a == &event.from[..20]) && (&event.to != &event.from)
-            }) {
+            if is_treasury_transfer(&event.from, &event.to) {
                 return vec![new_erc721_transfer(&hash, call_index, log, event)];
             }
         }
 
         if let Some(event) = ERC1155TransferSingleEvent::match_and_decode(log) {
-            if TREASURY_ADDRESSES.iter().any(|&a| {
-                (a == &event.to[..20] || a == &event.from[..20]) && (&event.to != &event.from)
-            }) {
+            if is_treasury_transfer(&event.from, &event.to) {
                 return vec![new_erc1155_single_transfer(&hash, call_index, log, event)];
             }
         }
 
         if let Some(event) = ERC1155TransferBatchEvent::match_and_decode(log) {
-            if TREASURY_ADDRESSES.iter().any(|&a| {
-                (a == &event.to[..20] || a == &event.from[..20]) && (&event.to != &event.from)
-            }) {
+            if is_treasury_transfer(&event.from, &event.to) {
                 return new_erc1155_batch_transfer(&hash, call_index, log, event);
             }
         }
 
         // WETH Deposit/Withdrawl
         if let Some(event) = WETHDepositEvent::match_and_decode(log) {
-            if TREASURY_ADDRESSES.iter().any(|&a| a == &event.dst[..20]) {
+            if is_treasury_address(&event.dst[..20], discovered_accounts) {
                 return vec![new_weth_deposit(&hash, call_index, log, event)];
             }
         }
         if let Some(event) = WETHWithdrawlEvent::match_and_decode(log) {
-            if TREASURY_ADDRESSES.iter().any(|&a| a == &event.src[..20]) {
+            if is_treasury_address(&event.src[..20], discovered_accounts) {
                 return vec![new_weth_withdrawal(&hash, call_index, log, event)];
             }
         }
@@ -264,11 +3162,15 @@ fn get_call_traces(trace: &eth::TransactionTrace) -> CallTraceRecord {
         hash: Hex(&trace.hash).to_string(),
         index: trace.index,
         traces: format!("'{}'", serde_json::to_string(&traces).unwrap().to_string()),
+        dao_id: "".to_string(),
     }
 }
 
 //Check for POW block rewards
-fn new_value_transfer_block_rewards_from_block(blk: &eth::Block) -> Option<Vec<ValueTransfer>> {
+fn new_value_transfer_block_rewards_from_block(
+    blk: &eth::Block,
+    discovered_accounts: &StoreGetProto<DiscoveredAccount>,
+) -> Option<Vec<ValueTransfer>> {
     let mut transfers: Vec<ValueTransfer> = Vec::new();
     let mut reward_value: BigInt = substreams::scalar::BigInt::zero();
     let mut reward_address = String::new();
@@ -284,7 +3186,7 @@ fn new_value_transfer_block_rewards_from_block(blk: &eth::Block) -> Option<Vec<V
                 || b.reason == eth::balance_change::Reason::RewardTransactionFee as i32
                 || b.reason == eth::balance_change::Reason::Withdrawal as i32
         })
-        .filter(|b| TREASURY_ADDRESSES.iter().any(|a| a.to_vec() == b.address))
+        .filter(|b| is_treasury_address(&b.address, discovered_accounts))
         .collect::<Vec<_>>()
     {
         let new_value = balance_change
@@ -301,16 +3203,18 @@ fn new_value_transfer_block_rewards_from_block(blk: &eth::Block) -> Option<Vec<V
 
         match eth::balance_change::Reason::from_i32(balance_change.reason).unwrap() {
             eth::balance_change::Reason::Withdrawal => {
-                let (_, value) = (old_value - new_value).to_bytes_be();
                 transfers.push(ValueTransfer {
                     call_index: 0,
                     from: "".to_string(),
                     to: Hex(&balance_change.address).to_string(),
-                    value: Hex(value).to_string(),
+                    value: unsigned_decimal(&(old_value - new_value)),
                     hash: Hex(&blk.hash).to_string(),
                     tx_index: 0,
                     input: "".to_string(),
                     reason: balance_change.reason,
+                    dao_id: "".to_string(),
+                    ordinal: balance_change.ordinal,
+                    value_usd: "".to_string(),
                 });
             }
 
@@ -327,6 +3231,7 @@ fn new_value_transfer_block_rewards_from_block(blk: &eth::Block) -> Option<Vec<V
     // Check Transactions for transaction rewards
     let mut reward_value: BigInt = substreams::scalar::BigInt::zero();
     let mut reward_address = String::new();
+    let mut reward_ordinal: u64 = 0;
 
     for trace in blk.transaction_traces.iter() {
         let calls = &trace.calls;
@@ -335,7 +3240,7 @@ fn new_value_transfer_block_rewards_from_block(blk: &eth::Block) -> Option<Vec<V
         for balance_change in root_call
             .balance_changes
             .iter()
-            .filter(|b| TREASURY_ADDRESSES.iter().any(|a| a.to_vec() == b.address))
+            .filter(|b| is_treasury_address(&b.address, discovered_accounts))
             .filter(|b| {
                 eth::balance_change::Reason::from_i32(b.reason).unwrap()
                     == eth::balance_change::Reason::RewardTransactionFee
@@ -358,20 +3263,23 @@ fn new_value_transfer_block_rewards_from_block(blk: &eth::Block) -> Option<Vec<V
                 reward_address = Hex(&balance_change.address).to_string();
             }
             reward_value = reward_value + new_value - old_value;
+            reward_ordinal = balance_change.ordinal;
         }
     }
 
     if reward_value > BigInt::zero() {
-        let (_, value) = reward_value.to_bytes_be();
         transfers.push(ValueTransfer {
             call_index: 0,
             from: "".to_string(),
             to: reward_address,
-            value: Hex(value).to_string(),
+            value: reward_value.to_string(),
             hash: Hex(&blk.hash).to_string(),
             tx_index: 0,
             input: "".to_string(),
             reason: eth::balance_change::Reason::RewardTransactionFee as i32,
+            dao_id: "".to_string(),
+            ordinal: reward_ordinal,
+            value_usd: "".to_string(),
         });
     }
 
@@ -386,6 +3294,7 @@ fn new_value_transfer_block_rewards_from_block(blk: &eth::Block) -> Option<Vec<V
 fn new_gas_transfer_from_call(
     trace: &eth::TransactionTrace,
     root_call: &eth::Call,
+    discovered_accounts: &StoreGetProto<DiscoveredAccount>,
 ) -> Option<Vec<ValueTransfer>> {
     let mut transfers: Vec<ValueTransfer> = Vec::new();
     let mut gas_value: BigInt = substreams::scalar::BigInt::zero();
@@ -394,7 +3303,7 @@ fn new_gas_transfer_from_call(
     for balance_change in root_call
         .balance_changes
         .iter()
-        .filter(|b| TREASURY_ADDRESSES.iter().any(|a| a.to_vec() == b.address))
+        .filter(|b| is_treasury_address(&b.address, discovered_accounts))
         .filter(|b| {
             b.reason != eth::balance_change::Reason::Transfer as i32
                 && b.reason != eth::balance_change::Reason::RewardTransactionFee as i32
@@ -438,16 +3347,18 @@ fn new_gas_transfer_from_call(
                             from_addr = Hex(&balance_change.address).to_string();
                         }
 
-                        let (_, val_bytes) = value_change.to_bytes_be();
                         transfers.push(ValueTransfer {
                             call_index: balance_change.ordinal as u32,
                             from: from_addr,
                             to: to_addr,
-                            value: bytes_to_hex(&val_bytes),
+                            value: unsigned_decimal(&value_change),
                             hash: Hex(&trace.hash).to_string(),
                             tx_index: trace.index,
                             input: "".to_string(),
                             reason: reason as i32,
+                            dao_id: "".to_string(),
+                            ordinal: balance_change.ordinal,
+                            value_usd: "".to_string(),
                         });
                     }
                 }
@@ -470,16 +3381,18 @@ fn new_gas_transfer_from_call(
             reason = eth::balance_change::Reason::GasBuy;
         }
 
-        let (_, value) = gas_value.to_bytes_be();
         transfers.push(ValueTransfer {
             call_index: trace.end_ordinal as u32,
             from: from_addr,
             to: to_addr,
-            value: Hex(value).to_string(),
+            value: unsigned_decimal(&gas_value),
             hash: Hex(&trace.hash).to_string(),
             tx_index: trace.index,
             input: "".to_string(),
             reason: reason as i32,
+            dao_id: "".to_string(),
+            ordinal: trace.end_ordinal,
+            value_usd: "".to_string(),
         });
     }
 
@@ -491,14 +3404,13 @@ fn new_value_transfer_from_call(
     call: &eth::Call,
 ) -> Option<ValueTransfer> {
     let v = match &call.value {
-        Some(_) => Hex(&call.value.as_ref().unwrap().bytes).to_string(),
-        None => {
+        Some(value) if !value.bytes.is_empty() => {
+            BigInt::from_unsigned_bytes_be(&value.bytes).to_string()
+        }
+        _ => {
             return None;
         }
     };
-    if v == "" {
-        return None;
-    }
     Some(ValueTransfer {
         call_index: call.index,
         from: Hex(&call.caller).to_string(),
@@ -508,6 +3420,9 @@ fn new_value_transfer_from_call(
         tx_index: trace.index,
         input: Hex(&call.input).to_string(),
         reason: eth::balance_change::Reason::Transfer as i32,
+        dao_id: "".to_string(),
+        ordinal: call.begin_ordinal,
+        value_usd: "".to_string(),
     })
 }
 
@@ -517,16 +3432,19 @@ fn new_erc20_transfer(
     log: &eth::Log,
     event: ERC20TransferEvent,
 ) -> TokenTransfer {
-    let (_, val_bytes) = event.value.to_bytes_be();
     TokenTransfer {
         from: Hex(&event.from).to_string(),
         to: Hex(&event.to).to_string(),
-        value: bytes_to_hex(&val_bytes),
+        value: event.value.to_string(),
         tx_hash: Hex(hash).to_string(),
         call_index: call_index,
         log_index: log.block_index as u64,
         token_address: Hex(&log.address).to_string(),
         token_id: "".to_string(),
+        dao_id: "".to_string(),
+        ordinal: log.ordinal,
+        value_usd: "".to_string(),
+        reason: "".to_string(),
     }
 }
 
@@ -539,12 +3457,16 @@ fn new_erc721_transfer(
     TokenTransfer {
         from: Hex(&event.from).to_string(),
         to: Hex(&event.to).to_string(),
-        value: "01".to_string(),
+        value: "1".to_string(),
         tx_hash: Hex(hash).to_string(),
         call_index: call_index,
         log_index: log.block_index as u64,
         token_id: event.token_id.to_string(),
         token_address: Hex(&log.address).to_string(),
+        dao_id: "".to_string(),
+        ordinal: log.ordinal,
+        value_usd: "".to_string(),
+        reason: "".to_string(),
     }
 }
 
@@ -578,18 +3500,19 @@ fn new_erc1155_batch_transfer(
         .ids
         .iter()
         .enumerate()
-        .map(|(i, id)| {
-            let (_, val_bytes) = event.values.get(i).unwrap().to_bytes_be();
-            TokenTransfer {
-                from: Hex(&event.from).to_string(),
-                to: Hex(&event.to).to_string(),
-                value: bytes_to_hex(&val_bytes),
-                tx_hash: Hex(hash).to_string(),
-                call_index: call_index,
-                log_index: log.block_index as u64,
-                token_address: Hex(&log.address).to_string(),
-                token_id: id.to_string(),
-            }
+        .map(|(i, id)| TokenTransfer {
+            from: Hex(&event.from).to_string(),
+            to: Hex(&event.to).to_string(),
+            value: event.values.get(i).unwrap().to_string(),
+            tx_hash: Hex(hash).to_string(),
+            call_index: call_index,
+            log_index: log.block_index as u64,
+            token_address: Hex(&log.address).to_string(),
+            token_id: id.to_string(),
+            dao_id: "".to_string(),
+            ordinal: log.ordinal,
+            value_usd: "".to_string(),
+            reason: "".to_string(),
         })
         .collect()
 }
@@ -600,16 +3523,19 @@ fn new_erc1155_transfer(
     log: &eth::Log,
     event: ERC1155TransferSingleEvent,
 ) -> TokenTransfer {
-    let (_, val_bytes) = event.value.to_bytes_be();
     TokenTransfer {
         from: Hex(event.from).to_string(),
         to: Hex(event.to).to_string(),
         call_index: call_index,
-        value: bytes_to_hex(&val_bytes),
+        value: event.value.to_string(),
         tx_hash: Hex(hash).to_string(),
         log_index: log.block_index as u64,
         token_address: Hex(&log.address).to_string(),
         token_id: event.id.to_string(),
+        dao_id: "".to_string(),
+        ordinal: log.ordinal,
+        value_usd: "".to_string(),
+        reason: "".to_string(),
     }
 }
 
@@ -619,16 +3545,19 @@ fn new_weth_deposit(
     log: &eth::Log,
     event: WETHDepositEvent,
 ) -> TokenTransfer {
-    let (_, val_bytes) = event.wad.to_bytes_be();
     TokenTransfer {
         from: Hex(NULL_ADDRESS).to_string(),
         to: Hex(&event.dst).to_string(),
-        value: bytes_to_hex(&val_bytes),
+        value: event.wad.to_string(),
         tx_hash: Hex(hash).to_string(),
         call_index: call_index,
         log_index: log.block_index as u64,
         token_address: Hex(&log.address).to_string(),
         token_id: "".to_string(),
+        dao_id: "".to_string(),
+        ordinal: log.ordinal,
+        value_usd: "".to_string(),
+        reason: "".to_string(),
     }
 }
 
@@ -638,19 +3567,59 @@ fn new_weth_withdrawal(
     log: &eth::Log,
     event: WETHWithdrawlEvent,
 ) -> TokenTransfer {
-    let (_, val_bytes) = event.wad.to_bytes_be();
     TokenTransfer {
         to: Hex(NULL_ADDRESS).to_string(),
         from: Hex(&event.src).to_string(),
-        value: bytes_to_hex(&val_bytes),
+        value: event.wad.to_string(),
         tx_hash: Hex(hash).to_string(),
         call_index: call_index,
         log_index: log.block_index as u64,
         token_address: Hex(&log.address).to_string(),
         token_id: "".to_string(),
+        dao_id: "".to_string(),
+        ordinal: log.ordinal,
+        value_usd: "".to_string(),
+        reason: "".to_string(),
+    }
+}
+
+// Base-10 representation of the magnitude of a value.  The direction is given by the from and to addresses
+fn unsigned_decimal(val: &BigInt) -> String {
+    let (_, val_bytes) = val.to_bytes_be();
+    BigInt::from_unsigned_bytes_be(&val_bytes).to_string()
+}
+
+// Unsigned event field as i64, which fits a bigint column, or None when out of range: BigInt::to_u64 panics,
+// halting the substream
+fn checked_i64(val: &BigInt) -> Option<i64> {
+    if *val < BigInt::zero() || *val > BigInt::from(i64::MAX) {
+        return None;
+    }
+    Some(val.to_u64() as i64)
+}
+
+fn token_transfer_v2(t: &TokenTransfer) -> v2::TokenTransfer {
+    v2::TokenTransfer {
+        ordinal: t.ordinal,
+        tx_hash: hex_to_bytes(&t.tx_hash),
+        call_index: t.call_index,
+        log_index: t.log_index,
+        from: hex_to_bytes(&t.from),
+        to: hex_to_bytes(&t.to),
+        value: t.value.to_string(),
+        token_address: hex_to_bytes(&t.token_address),
+        token_id: t.token_id.to_string(),
+        dao_id: t.dao_id.to_string(),
+        value_usd: t.value_usd.to_string(),
+        reason: t.reason.to_string(),
     }
 }
 
+// Addresses and hashes of frenscan.types.v1 are produced by Hex, so always decode
+fn hex_to_bytes(val: &String) -> Vec<u8> {
+    hex::decode(val).unwrap()
+}
+
 fn bytes_to_hex(val: &Vec<u8>) -> String {
     let v = Hex(&val).to_string();
     if v.chars().count() % 2 == 0 {
@@ -35,7 +35,8 @@ pub struct TokenTransfer {
     /// The person that received the transfer.
     #[prost(string, tag = "5")]
     pub to: ::prost::alloc::string::String,
-    /// How many token were transferred in this transfer, will always be 1 in the case of ERC721.
+    /// How many token were transferred in this transfer, as a base-10 string in the smallest unit of the token.
+    /// Will always be 1 in the case of ERC721.
     #[prost(string, tag = "6")]
     pub value: ::prost::alloc::string::String,
     /// Token Address
@@ -46,6 +47,19 @@ pub struct TokenTransfer {
     /// will be empty string "" as the contract itself is the token identifier.
     #[prost(string, tag = "8")]
     pub token_id: ::prost::alloc::string::String,
+    /// The DAO whose account or issued token is party to the transfer
+    #[prost(string, tag = "9")]
+    pub dao_id: ::prost::alloc::string::String,
+    /// Firehose ordinal of the log, unique within the block
+    #[prost(uint64, tag = "10")]
+    pub ordinal: u64,
+    /// USD value of the transfer at the time it executed, through the price pools.  Empty when the token has no price
+    #[prost(string, tag = "11")]
+    pub value_usd: ::prost::alloc::string::String,
+    /// Why the balance changed: empty for a Transfer event, rebase for the change of the balance of a rebasing token
+    /// between its events
+    #[prost(string, tag = "12")]
+    pub reason: ::prost::alloc::string::String,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -66,13 +80,23 @@ pub struct ValueTransfer {
     /// The person that received the transfer.
     #[prost(string, tag = "5")]
     pub to: ::prost::alloc::string::String,
-    /// How many token were transferred in this transfer, will always be 1 in the case of ERC721.
+    /// How many token were transferred in this transfer, as a base-10 string in the smallest unit of the token.
+    /// Will always be 1 in the case of ERC721.
     #[prost(string, tag = "6")]
     pub value: ::prost::alloc::string::String,
     #[prost(string, tag = "7")]
     pub input: ::prost::alloc::string::String,
     #[prost(int32, tag = "8")]
     pub reason: i32,
+    /// The DAO whose account is party to the transfer
+    #[prost(string, tag = "9")]
+    pub dao_id: ::prost::alloc::string::String,
+    /// Firehose ordinal of the balance change, call or transaction end, unique within the block
+    #[prost(uint64, tag = "10")]
+    pub ordinal: u64,
+    /// USD value of the transfer at the time it executed, through the price pools.  Empty when ETH has no price
+    #[prost(string, tag = "11")]
+    pub value_usd: ::prost::alloc::string::String,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -84,6 +108,8 @@ pub struct CallTraceRecord {
     /// Transaction calls in JSON
     #[prost(string, tag = "3")]
     pub traces: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub dao_id: ::prost::alloc::string::String,
 }
 #[derive(::serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -127,4 +153,746 @@ pub struct CallTrace {
     #[prost(bool, tag = "17")]
     pub state_reverted: bool,
 }
-// @@protoc_insertion_point(module)
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DiscoveredAccounts {
+    #[prost(uint64, tag = "1")]
+    pub block_number: u64,
+    #[prost(message, repeated, tag = "2")]
+    pub accounts: ::prost::alloc::vec::Vec<DiscoveredAccount>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DiscoveredAccount {
+    /// Address of the new contract
+    #[prost(string, tag = "1")]
+    pub address: ::prost::alloc::string::String,
+    /// Treasury account or factory contract which created the account
+    #[prost(string, tag = "2")]
+    pub creator: ::prost::alloc::string::String,
+    /// Name of the factory in frens.yaml.  Empty when created directly by a treasury account
+    #[prost(string, tag = "3")]
+    pub factory: ::prost::alloc::string::String,
+    /// The transaction hash that created the account
+    #[prost(string, tag = "4")]
+    pub tx_hash: ::prost::alloc::string::String,
+    /// Block in which the account was created.  Transfers are tracked from this block onward
+    #[prost(uint64, tag = "5")]
+    pub block_number: u64,
+    /// The DAO the account is added to
+    #[prost(string, tag = "6")]
+    pub dao_id: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PoolPrices {
+    #[prost(uint64, tag = "1")]
+    pub block_number: u64,
+    #[prost(message, repeated, tag = "2")]
+    pub prices: ::prost::alloc::vec::Vec<PoolPrice>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PoolPrice {
+    /// Address of the price pool
+    #[prost(string, tag = "1")]
+    pub pool_address: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub token0: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub token1: ::prost::alloc::string::String,
+    /// Price of token0 in token1, adjusted by the decimals of the tokens
+    #[prost(string, tag = "4")]
+    pub price0: ::prost::alloc::string::String,
+    /// Firehose ordinal of the Sync or Swap log
+    #[prost(uint64, tag = "5")]
+    pub ordinal: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FeedPrices {
+    #[prost(uint64, tag = "1")]
+    pub block_number: u64,
+    #[prost(int64, tag = "2")]
+    pub block_timestamp: i64,
+    #[prost(message, repeated, tag = "3")]
+    pub prices: ::prost::alloc::vec::Vec<FeedPrice>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FeedPrice {
+    /// Address of the Chainlink aggregator
+    #[prost(string, tag = "1")]
+    pub feed_address: ::prost::alloc::string::String,
+    /// Token priced by the feed, ETH or a token address
+    #[prost(string, tag = "2")]
+    pub token_address: ::prost::alloc::string::String,
+    /// Answer of the feed in USD, adjusted by the decimals of the feed
+    #[prost(string, tag = "3")]
+    pub price: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub round_id: ::prost::alloc::string::String,
+    /// Timestamp of the round, from the AnswerUpdated event.  0 when out of range
+    #[prost(int64, tag = "5")]
+    pub updated_at: i64,
+    /// Firehose ordinal of the AnswerUpdated log
+    #[prost(uint64, tag = "6")]
+    pub ordinal: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct NetWorths {
+    #[prost(uint64, tag = "1")]
+    pub block_number: u64,
+    #[prost(int64, tag = "2")]
+    pub block_timestamp: i64,
+    /// Day of the snapshot, in days since the Unix epoch.  Snapshots are taken at the first block of each day
+    #[prost(int64, tag = "3")]
+    pub day: i64,
+    #[prost(message, repeated, tag = "4")]
+    pub values: ::prost::alloc::vec::Vec<AccountValue>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AccountValue {
+    #[prost(string, tag = "1")]
+    pub dao_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub address: ::prost::alloc::string::String,
+    /// ETH or the token address
+    #[prost(string, tag = "3")]
+    pub token_address: ::prost::alloc::string::String,
+    /// Balance in the smallest unit of the token, as a base-10 string
+    #[prost(string, tag = "4")]
+    pub balance: ::prost::alloc::string::String,
+    /// USD value of the balance at the price of the snapshot block
+    #[prost(string, tag = "5")]
+    pub value_usd: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PeriodSnapshots {
+    #[prost(uint64, tag = "1")]
+    pub block_number: u64,
+    #[prost(int64, tag = "2")]
+    pub block_timestamp: i64,
+    #[prost(message, repeated, tag = "3")]
+    pub snapshots: ::prost::alloc::vec::Vec<PeriodSnapshot>,
+}
+/// Balance and flows of an account and token over a UTC day or month, as of the block.  Amounts are base-10 strings
+/// in the smallest unit of the token
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PeriodSnapshot {
+    /// day or month
+    #[prost(string, tag = "1")]
+    pub period: ::prost::alloc::string::String,
+    /// First day of the period, as YYYY-MM-DD
+    #[prost(string, tag = "2")]
+    pub start_date: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub dao_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub address: ::prost::alloc::string::String,
+    /// ETH or the token address
+    #[prost(string, tag = "5")]
+    pub token_address: ::prost::alloc::string::String,
+    #[prost(string, tag = "6")]
+    pub opening_balance: ::prost::alloc::string::String,
+    #[prost(string, tag = "7")]
+    pub inflow: ::prost::alloc::string::String,
+    /// Outgoing transfers, excluding fees
+    #[prost(string, tag = "8")]
+    pub outflow: ::prost::alloc::string::String,
+    /// Gas paid, net of refunds
+    #[prost(string, tag = "9")]
+    pub fees: ::prost::alloc::string::String,
+    #[prost(string, tag = "10")]
+    pub closing_balance: ::prost::alloc::string::String,
+    /// First snapshot of the account and token in the period
+    #[prost(bool, tag = "11")]
+    pub first: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Delegations {
+    #[prost(uint64, tag = "1")]
+    pub block_number: u64,
+    #[prost(int64, tag = "2")]
+    pub block_timestamp: i64,
+    #[prost(message, repeated, tag = "3")]
+    pub delegate_changes: ::prost::alloc::vec::Vec<DelegateChange>,
+    #[prost(message, repeated, tag = "4")]
+    pub votes_changes: ::prost::alloc::vec::Vec<DelegateVotesChange>,
+}
+/// ERC20Votes DelegateChanged event of an issued token
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DelegateChange {
+    #[prost(string, tag = "1")]
+    pub dao_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub token_address: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub tx_hash: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "4")]
+    pub log_index: u64,
+    #[prost(string, tag = "5")]
+    pub delegator: ::prost::alloc::string::String,
+    #[prost(string, tag = "6")]
+    pub from_delegate: ::prost::alloc::string::String,
+    #[prost(string, tag = "7")]
+    pub to_delegate: ::prost::alloc::string::String,
+    /// Firehose ordinal of the DelegateChanged log
+    #[prost(uint64, tag = "8")]
+    pub ordinal: u64,
+}
+/// ERC20Votes DelegateVotesChanged event of an issued token.  Votes are base-10 strings in the smallest unit of the token
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DelegateVotesChange {
+    #[prost(string, tag = "1")]
+    pub dao_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub token_address: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub tx_hash: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "4")]
+    pub log_index: u64,
+    #[prost(string, tag = "5")]
+    pub delegate: ::prost::alloc::string::String,
+    #[prost(string, tag = "6")]
+    pub previous_votes: ::prost::alloc::string::String,
+    #[prost(string, tag = "7")]
+    pub new_votes: ::prost::alloc::string::String,
+    /// Firehose ordinal of the DelegateVotesChanged log
+    #[prost(uint64, tag = "8")]
+    pub ordinal: u64,
+}#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GovernanceEvents {
+    #[prost(uint64, tag = "1")]
+    pub block_number: u64,
+    #[prost(int64, tag = "2")]
+    pub block_timestamp: i64,
+    #[prost(message, repeated, tag = "3")]
+    pub proposals: ::prost::alloc::vec::Vec<Proposal>,
+    #[prost(message, repeated, tag = "4")]
+    pub votes: ::prost::alloc::vec::Vec<Vote>,
+    #[prost(message, repeated, tag = "5")]
+    pub state_changes: ::prost::alloc::vec::Vec<ProposalStateChange>,
+}
+/// ProposalCreated event of a governor
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Proposal {
+    #[prost(string, tag = "1")]
+    pub dao_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub governor_address: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub proposal_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub tx_hash: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "5")]
+    pub log_index: u64,
+    #[prost(string, tag = "6")]
+    pub proposer: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "7")]
+    pub actions: ::prost::alloc::vec::Vec<ProposalAction>,
+    /// Blocks, or timestamps for governors with a timestamp clock, of the voting period
+    #[prost(string, tag = "8")]
+    pub vote_start: ::prost::alloc::string::String,
+    #[prost(string, tag = "9")]
+    pub vote_end: ::prost::alloc::string::String,
+    #[prost(string, tag = "10")]
+    pub description: ::prost::alloc::string::String,
+    /// Firehose ordinal of the ProposalCreated log
+    #[prost(uint64, tag = "11")]
+    pub ordinal: u64,
+}
+/// Call made by the proposal when executed
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ProposalAction {
+    #[prost(string, tag = "1")]
+    pub target: ::prost::alloc::string::String,
+    /// ETH sent with the call, in wei
+    #[prost(string, tag = "2")]
+    pub value: ::prost::alloc::string::String,
+    /// Function signature, empty when the calldata includes the selector
+    #[prost(string, tag = "3")]
+    pub signature: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub calldata: ::prost::alloc::string::String,
+}
+/// VoteCast or VoteCastWithParams event of a governor
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Vote {
+    #[prost(string, tag = "1")]
+    pub dao_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub governor_address: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub proposal_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub tx_hash: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "5")]
+    pub log_index: u64,
+    #[prost(string, tag = "6")]
+    pub voter: ::prost::alloc::string::String,
+    /// 0 against, 1 for, 2 abstain
+    #[prost(uint32, tag = "7")]
+    pub support: u32,
+    /// Votes of the voter, in the smallest unit of the token
+    #[prost(string, tag = "8")]
+    pub weight: ::prost::alloc::string::String,
+    #[prost(string, tag = "9")]
+    pub reason: ::prost::alloc::string::String,
+    #[prost(string, tag = "10")]
+    pub params: ::prost::alloc::string::String,
+    /// Firehose ordinal of the VoteCast log
+    #[prost(uint64, tag = "11")]
+    pub ordinal: u64,
+}
+/// ProposalQueued, ProposalExecuted or ProposalCanceled event of a governor
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ProposalStateChange {
+    #[prost(string, tag = "1")]
+    pub dao_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub governor_address: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub proposal_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub tx_hash: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "5")]
+    pub log_index: u64,
+    /// queued, executed or canceled
+    #[prost(string, tag = "6")]
+    pub state: ::prost::alloc::string::String,
+    /// Timestamp after which a queued proposal may be executed, 0 for the other states
+    #[prost(uint64, tag = "7")]
+    pub eta: u64,
+    /// Firehose ordinal of the log
+    #[prost(uint64, tag = "8")]
+    pub ordinal: u64,
+}#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TimelockOperations {
+    #[prost(uint64, tag = "1")]
+    pub block_number: u64,
+    #[prost(int64, tag = "2")]
+    pub block_timestamp: i64,
+    #[prost(message, repeated, tag = "3")]
+    pub operations: ::prost::alloc::vec::Vec<TimelockOperation>,
+}
+/// Call scheduled, executed or canceled by a TimelockController or Compound Timelock
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TimelockOperation {
+    #[prost(string, tag = "1")]
+    pub dao_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub timelock_address: ::prost::alloc::string::String,
+    /// Operation id of a TimelockController, or the txHash of a Compound Timelock
+    #[prost(string, tag = "3")]
+    pub operation_id: ::prost::alloc::string::String,
+    /// Index of the call in a TimelockController batch, 0 for a Compound Timelock
+    #[prost(uint64, tag = "4")]
+    pub call_index: u64,
+    #[prost(string, tag = "5")]
+    pub tx_hash: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "6")]
+    pub log_index: u64,
+    /// scheduled, executed or canceled
+    #[prost(string, tag = "7")]
+    pub state: ::prost::alloc::string::String,
+    /// Call made by the operation.  Empty for a TimelockController Cancelled event, which only has the operation id
+    #[prost(string, tag = "8")]
+    pub target: ::prost::alloc::string::String,
+    #[prost(string, tag = "9")]
+    pub value: ::prost::alloc::string::String,
+    #[prost(string, tag = "10")]
+    pub signature: ::prost::alloc::string::String,
+    #[prost(string, tag = "11")]
+    pub data: ::prost::alloc::string::String,
+    #[prost(string, tag = "12")]
+    pub predecessor: ::prost::alloc::string::String,
+    /// Timestamp after which the operation may be executed
+    #[prost(uint64, tag = "13")]
+    pub eta: u64,
+    /// Recipient and amount when the call is an ERC-20 transfer(address,uint256) of the target token
+    #[prost(string, tag = "14")]
+    pub transfer_to: ::prost::alloc::string::String,
+    #[prost(string, tag = "15")]
+    pub transfer_amount: ::prost::alloc::string::String,
+    /// Firehose ordinal of the log
+    #[prost(uint64, tag = "16")]
+    pub ordinal: u64,
+}#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ShareChanges {
+    #[prost(uint64, tag = "1")]
+    pub block_number: u64,
+    #[prost(int64, tag = "2")]
+    pub block_timestamp: i64,
+    #[prost(message, repeated, tag = "3")]
+    pub share_changes: ::prost::alloc::vec::Vec<ShareChange>,
+    #[prost(message, repeated, tag = "4")]
+    pub share_rates: ::prost::alloc::vec::Vec<ShareRate>,
+}
+/// Change of the shares held by a DAO account in a rebasing token
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ShareChange {
+    #[prost(string, tag = "1")]
+    pub dao_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub address: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub token_address: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub tx_hash: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "5")]
+    pub log_index: u64,
+    /// Signed base-10 change of the shares, or of the scaled balance of an aToken
+    #[prost(string, tag = "6")]
+    pub shares: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "7")]
+    pub ordinal: u64,
+}
+/// Balance worth one share of a rebasing token, as numerator / denominator
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ShareRate {
+    #[prost(string, tag = "1")]
+    pub token_address: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub tx_hash: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "3")]
+    pub log_index: u64,
+    /// Total pooled ether and total shares of Lido, or the liquidity index and 1e27 of an aToken
+    #[prost(string, tag = "4")]
+    pub numerator: ::prost::alloc::string::String,
+    #[prost(string, tag = "5")]
+    pub denominator: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "6")]
+    pub ordinal: u64,
+}#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VaultEvents {
+    #[prost(uint64, tag = "1")]
+    pub block_number: u64,
+    #[prost(int64, tag = "2")]
+    pub block_timestamp: i64,
+    #[prost(message, repeated, tag = "3")]
+    pub events: ::prost::alloc::vec::Vec<VaultEvent>,
+}
+/// ERC-4626 Deposit or Withdraw event whose owner or receiver is a DAO account
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VaultEvent {
+    #[prost(string, tag = "1")]
+    pub dao_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub vault_address: ::prost::alloc::string::String,
+    /// Token transferred into the vault by a deposit, or out of it by a withdrawal, in the same transaction.  Empty
+    /// when no such transfer was found
+    #[prost(string, tag = "3")]
+    pub asset_address: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub tx_hash: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "5")]
+    pub log_index: u64,
+    /// deposit or withdraw
+    #[prost(string, tag = "6")]
+    pub kind: ::prost::alloc::string::String,
+    #[prost(string, tag = "7")]
+    pub sender: ::prost::alloc::string::String,
+    /// Receiver of the assets of a withdrawal, the owner for a deposit
+    #[prost(string, tag = "8")]
+    pub receiver: ::prost::alloc::string::String,
+    /// Account whose shares were minted or burned
+    #[prost(string, tag = "9")]
+    pub owner: ::prost::alloc::string::String,
+    /// Base-10 amounts in the smallest unit of the asset and of the vault shares
+    #[prost(string, tag = "10")]
+    pub assets: ::prost::alloc::string::String,
+    #[prost(string, tag = "11")]
+    pub shares: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "12")]
+    pub ordinal: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VaultPrices {
+    #[prost(uint64, tag = "1")]
+    pub block_number: u64,
+    #[prost(int64, tag = "2")]
+    pub block_timestamp: i64,
+    #[prost(message, repeated, tag = "3")]
+    pub prices: ::prost::alloc::vec::Vec<VaultPrice>,
+}
+/// Assets per share of a vault known from the DAO events, given by any of its Deposit or Withdraw events
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VaultPrice {
+    #[prost(string, tag = "1")]
+    pub vault_address: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub assets: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub shares: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "4")]
+    pub ordinal: u64,
+}#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Trades {
+    #[prost(uint64, tag = "1")]
+    pub block_number: u64,
+    #[prost(int64, tag = "2")]
+    pub block_timestamp: i64,
+    #[prost(message, repeated, tag = "3")]
+    pub trades: ::prost::alloc::vec::Vec<TradeRecord>,
+}
+/// DEX swap made by or for a DAO account, pairing the token sold to the pool with the token bought from it
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TradeRecord {
+    #[prost(string, tag = "1")]
+    pub dao_id: ::prost::alloc::string::String,
+    /// The DAO account behind the swap: its recipient, its sender, the caller of its sender or the transaction sender
+    #[prost(string, tag = "2")]
+    pub account: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub tx_hash: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "4")]
+    pub log_index: u64,
+    /// uniswap_v2, uniswap_v3, curve or balancer_v2
+    #[prost(string, tag = "5")]
+    pub protocol: ::prost::alloc::string::String,
+    /// Address of the pool.  For Balancer, the first 20 bytes of the pool id
+    #[prost(string, tag = "6")]
+    pub pool_address: ::prost::alloc::string::String,
+    /// Contract which called the pool, and receiver of the bought tokens.  Balancer swaps have no recipient
+    #[prost(string, tag = "7")]
+    pub sender: ::prost::alloc::string::String,
+    #[prost(string, tag = "8")]
+    pub recipient: ::prost::alloc::string::String,
+    /// Tokens and base-10 amounts in their smallest unit
+    #[prost(string, tag = "9")]
+    pub sold_token: ::prost::alloc::string::String,
+    #[prost(string, tag = "10")]
+    pub sold_amount: ::prost::alloc::string::String,
+    #[prost(string, tag = "11")]
+    pub bought_token: ::prost::alloc::string::String,
+    #[prost(string, tag = "12")]
+    pub bought_amount: ::prost::alloc::string::String,
+    /// Bought tokens per sold token, adjusted for their decimals.  Empty when the decimals of either are unknown
+    #[prost(string, tag = "13")]
+    pub price: ::prost::alloc::string::String,
+    /// USD value of the sold tokens, or of the bought tokens when the sold ones have no price
+    #[prost(string, tag = "14")]
+    pub value_usd: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "15")]
+    pub ordinal: u64,
+}#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LendingEvents {
+    #[prost(uint64, tag = "1")]
+    pub block_number: u64,
+    #[prost(int64, tag = "2")]
+    pub block_timestamp: i64,
+    #[prost(message, repeated, tag = "3")]
+    pub events: ::prost::alloc::vec::Vec<LendingEvent>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LendingEvent {
+    #[prost(string, tag = "1")]
+    pub dao_id: ::prost::alloc::string::String,
+    /// The DAO account whose position changed
+    #[prost(string, tag = "2")]
+    pub account: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub tx_hash: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "4")]
+    pub log_index: u64,
+    /// aave_v2, aave_v3 or compound
+    #[prost(string, tag = "5")]
+    pub protocol: ::prost::alloc::string::String,
+    /// Aave pool or Compound cToken
+    #[prost(string, tag = "6")]
+    pub market_address: ::prost::alloc::string::String,
+    /// Token supplied or borrowed, ETH for cETH.  Empty for Compound liquidations, which only seize cTokens
+    #[prost(string, tag = "7")]
+    pub asset_address: ::prost::alloc::string::String,
+    /// supply, withdraw, borrow, repay or liquidation
+    #[prost(string, tag = "8")]
+    pub kind: ::prost::alloc::string::String,
+    /// collateral or debt
+    #[prost(string, tag = "9")]
+    pub side: ::prost::alloc::string::String,
+    /// Assets moved, in their smallest unit.  Empty for Compound liquidations
+    #[prost(string, tag = "10")]
+    pub amount: ::prost::alloc::string::String,
+    /// cTokens minted, redeemed or seized.  Empty for Aave
+    #[prost(string, tag = "11")]
+    pub tokens: ::prost::alloc::string::String,
+    /// Debt of the account after a Compound borrow or repay, interest included.  Empty otherwise
+    #[prost(string, tag = "12")]
+    pub account_borrows: ::prost::alloc::string::String,
+    /// Caller, repayer, receiver or liquidator.  Empty for Compound supplies, withdrawals and borrows
+    #[prost(string, tag = "13")]
+    pub counterparty: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "14")]
+    pub ordinal: u64,
+}#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LpEvents {
+    #[prost(uint64, tag = "1")]
+    pub block_number: u64,
+    #[prost(int64, tag = "2")]
+    pub block_timestamp: i64,
+    #[prost(message, repeated, tag = "3")]
+    pub events: ::prost::alloc::vec::Vec<LpEvent>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LpEvent {
+    #[prost(string, tag = "1")]
+    pub dao_id: ::prost::alloc::string::String,
+    /// The DAO account owning the position NFT
+    #[prost(string, tag = "2")]
+    pub owner: ::prost::alloc::string::String,
+    /// Token id of the Uniswap V3 NonfungiblePositionManager NFT
+    #[prost(string, tag = "3")]
+    pub token_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub tx_hash: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "5")]
+    pub log_index: u64,
+    /// increase, decrease or collect
+    #[prost(string, tag = "6")]
+    pub kind: ::prost::alloc::string::String,
+    #[prost(string, tag = "7")]
+    pub pool_address: ::prost::alloc::string::String,
+    #[prost(int32, tag = "8")]
+    pub tick_lower: i32,
+    #[prost(int32, tag = "9")]
+    pub tick_upper: i32,
+    /// Tokens of the pool, when transferred to or from it with the event.  Empty otherwise
+    #[prost(string, tag = "10")]
+    pub token0: ::prost::alloc::string::String,
+    #[prost(string, tag = "11")]
+    pub token1: ::prost::alloc::string::String,
+    /// Liquidity added or removed.  Empty for collect
+    #[prost(string, tag = "12")]
+    pub liquidity: ::prost::alloc::string::String,
+    /// Tokens added, removed or collected, in their smallest unit
+    #[prost(string, tag = "13")]
+    pub amount0: ::prost::alloc::string::String,
+    #[prost(string, tag = "14")]
+    pub amount1: ::prost::alloc::string::String,
+    /// Receiver of the collected tokens.  Empty for increase and decrease
+    #[prost(string, tag = "15")]
+    pub recipient: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "16")]
+    pub ordinal: u64,
+}#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StreamEvents {
+    #[prost(uint64, tag = "1")]
+    pub block_number: u64,
+    #[prost(int64, tag = "2")]
+    pub block_timestamp: i64,
+    #[prost(message, repeated, tag = "3")]
+    pub events: ::prost::alloc::vec::Vec<StreamEvent>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StreamEvent {
+    #[prost(string, tag = "1")]
+    pub dao_id: ::prost::alloc::string::String,
+    /// sablier_v1, sablier_v2 or llamapay
+    #[prost(string, tag = "2")]
+    pub protocol: ::prost::alloc::string::String,
+    /// Sablier contract, or LlamaPay contract of the token
+    #[prost(string, tag = "3")]
+    pub contract_address: ::prost::alloc::string::String,
+    /// Sablier stream id, or LlamaPay stream id in hex
+    #[prost(string, tag = "4")]
+    pub stream_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "5")]
+    pub tx_hash: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "6")]
+    pub log_index: u64,
+    /// create, withdraw, cancel or pause
+    #[prost(string, tag = "7")]
+    pub kind: ::prost::alloc::string::String,
+    /// Payer and payee of the stream.  Empty when not known, for withdrawals of streams not created by a DAO account
+    #[prost(string, tag = "8")]
+    pub sender: ::prost::alloc::string::String,
+    #[prost(string, tag = "9")]
+    pub recipient: ::prost::alloc::string::String,
+    /// Token streamed.  Empty when not known, for LlamaPay streams until their first withdrawal
+    #[prost(string, tag = "10")]
+    pub token_address: ::prost::alloc::string::String,
+    /// Total amount streamed by a Sablier stream.  Empty for LlamaPay
+    #[prost(string, tag = "11")]
+    pub deposit: ::prost::alloc::string::String,
+    /// Tokens streamed per second.  In the smallest unit of the token for Sablier, and with 20 decimals for LlamaPay
+    #[prost(string, tag = "12")]
+    pub amount_per_sec: ::prost::alloc::string::String,
+    /// Unix timestamps of the stream.  LlamaPay streams start when created, and stop when cancelled or paused
+    #[prost(uint64, tag = "13")]
+    pub start_time: u64,
+    #[prost(uint64, tag = "14")]
+    pub cliff_time: u64,
+    #[prost(uint64, tag = "15")]
+    pub stop_time: u64,
+    /// Amount withdrawn, or due to the recipient on cancel, in the smallest unit of the token
+    #[prost(string, tag = "16")]
+    pub amount: ::prost::alloc::string::String,
+    /// Amount refunded to the sender on cancel
+    #[prost(string, tag = "17")]
+    pub refund: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "18")]
+    pub ordinal: u64,
+}#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Claims {
+    #[prost(uint64, tag = "1")]
+    pub block_number: u64,
+    #[prost(int64, tag = "2")]
+    pub block_timestamp: i64,
+    #[prost(message, repeated, tag = "3")]
+    pub claims: ::prost::alloc::vec::Vec<Claim>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Claim {
+    #[prost(string, tag = "1")]
+    pub dao_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub distributor_address: ::prost::alloc::string::String,
+    /// Airdropped token, from frens.yaml
+    #[prost(string, tag = "3")]
+    pub token_address: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub tx_hash: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "5")]
+    pub log_index: u64,
+    /// Index of the claim in the merkle tree
+    #[prost(uint64, tag = "6")]
+    pub claim_index: u64,
+    #[prost(string, tag = "7")]
+    pub account: ::prost::alloc::string::String,
+    #[prost(string, tag = "8")]
+    pub amount: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "9")]
+    pub ordinal: u64,
+}// @@protoc_insertion_point(module)
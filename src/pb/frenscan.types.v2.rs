@@ -0,0 +1,108 @@
+// @generated
+/// Transfers with raw bytes addresses and hashes.  Every record carries the Firehose ordinal at which the movement
+/// happened, so records of a block can be ordered and keyed without regard to their type.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Transfers {
+    #[prost(uint64, tag = "1")]
+    pub block_number: u64,
+    #[prost(int64, tag = "2")]
+    pub block_timestamp: i64,
+    #[prost(message, repeated, tag = "3")]
+    pub value_transfers: ::prost::alloc::vec::Vec<ValueTransfer>,
+    #[prost(message, repeated, tag = "4")]
+    pub token_transfers: ::prost::alloc::vec::Vec<TokenTransfer>,
+    #[prost(message, repeated, tag = "5")]
+    pub call_traces: ::prost::alloc::vec::Vec<CallTraceRecord>,
+    #[prost(message, repeated, tag = "6")]
+    pub issued_token_transfers: ::prost::alloc::vec::Vec<TokenTransfer>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TokenTransfer {
+    /// Firehose ordinal of the log.  Transfers of an ERC1155 TransferBatch share the ordinal of their log
+    #[prost(uint64, tag = "1")]
+    pub ordinal: u64,
+    /// The transaction hash that generated that transfer.
+    #[prost(bytes = "vec", tag = "2")]
+    pub tx_hash: ::prost::alloc::vec::Vec<u8>,
+    /// The index of the call in the transaction
+    #[prost(uint32, tag = "3")]
+    pub call_index: u32,
+    /// The index of the log within the block
+    #[prost(uint64, tag = "4")]
+    pub log_index: u64,
+    #[prost(bytes = "vec", tag = "5")]
+    pub from: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "6")]
+    pub to: ::prost::alloc::vec::Vec<u8>,
+    /// How many token were transferred in this transfer, as a base-10 string in the smallest unit of the token.
+    /// Will always be 1 in the case of ERC721.
+    #[prost(string, tag = "7")]
+    pub value: ::prost::alloc::string::String,
+    #[prost(bytes = "vec", tag = "8")]
+    pub token_address: ::prost::alloc::vec::Vec<u8>,
+    /// Empty for ERC20 tokens
+    #[prost(string, tag = "9")]
+    pub token_id: ::prost::alloc::string::String,
+    /// The DAO whose account or issued token is party to the transfer
+    #[prost(string, tag = "10")]
+    pub dao_id: ::prost::alloc::string::String,
+    /// USD value of the transfer at the time it executed.  Empty when the token has no price
+    #[prost(string, tag = "11")]
+    pub value_usd: ::prost::alloc::string::String,
+    /// Empty for a Transfer event, rebase for the change of the balance of a rebasing token between its events
+    #[prost(string, tag = "12")]
+    pub reason: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ValueTransfer {
+    /// Firehose ordinal of the balance change, call or transaction end, unique within the block
+    #[prost(uint64, tag = "1")]
+    pub ordinal: u64,
+    /// The transaction or block hash that generated that transfer, depending on reason
+    #[prost(bytes = "vec", tag = "2")]
+    pub hash: ::prost::alloc::vec::Vec<u8>,
+    /// The index of the transaction in the block
+    #[prost(uint32, tag = "3")]
+    pub tx_index: u32,
+    /// The index of the call in the transaction
+    #[prost(uint32, tag = "4")]
+    pub call_index: u32,
+    /// Empty for block rewards and withdrawals
+    #[prost(bytes = "vec", tag = "5")]
+    pub from: ::prost::alloc::vec::Vec<u8>,
+    /// Empty for gas payments and balance decreases
+    #[prost(bytes = "vec", tag = "6")]
+    pub to: ::prost::alloc::vec::Vec<u8>,
+    /// Amount of wei, as a base-10 string
+    #[prost(string, tag = "7")]
+    pub value: ::prost::alloc::string::String,
+    #[prost(bytes = "vec", tag = "8")]
+    pub input: ::prost::alloc::vec::Vec<u8>,
+    /// substreams_ethereum::pb::eth::v2::balance_change::Reason
+    #[prost(int32, tag = "9")]
+    pub reason: i32,
+    /// The DAO whose account is party to the transfer
+    #[prost(string, tag = "10")]
+    pub dao_id: ::prost::alloc::string::String,
+    /// USD value of the transfer at the time it executed.  Empty when ETH has no price
+    #[prost(string, tag = "11")]
+    pub value_usd: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CallTraceRecord {
+    #[prost(bytes = "vec", tag = "1")]
+    pub tx_hash: ::prost::alloc::vec::Vec<u8>,
+    /// The index of the transaction in the block
+    #[prost(uint32, tag = "2")]
+    pub tx_index: u32,
+    /// Transaction calls in JSON
+    #[prost(string, tag = "3")]
+    pub traces: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub dao_id: ::prost::alloc::string::String,
+}
+// @@protoc_insertion_point(module)
@@ -5,3 +5,11 @@ mod frenscan_priv;
 pub mod frenscan {
     pub use super::frenscan_priv::*;
 }
+
+#[path = "frenscan.types.v2.rs"]
+#[allow(dead_code)]
+mod frenscan_v2_priv;
+
+pub mod frenscan_v2 {
+    pub use super::frenscan_v2_priv::*;
+}
@@ -0,0 +1,55 @@
+use crate::frensfile::{parse_frens_file, FrensFile, TokenMetadata};
+use std::collections::HashMap;
+
+// DAO selected with --dao, defaulting to the first DAO of the frens.yaml file given with --frens-file
+pub struct Context {
+    pub organization: FrensFile,
+    pub schema_name: String,
+    pub table_prefix: String,
+    pub tokens: Vec<TokenMetadata>,
+}
+
+// Options of the form --name value
+pub fn parse_options(usage: &str) -> HashMap<String, String> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mut options: HashMap<String, String> = HashMap::new();
+    for pair in args.chunks(2) {
+        match pair {
+            [name, value] if name.starts_with("--") => {
+                options.insert(name[2..].to_string(), value.to_string());
+            }
+            _ => panic!("{}", usage),
+        }
+    }
+    options
+}
+
+pub fn load_context(options: &HashMap<String, String>) -> Context {
+    let frens_file_name = match options.get("frens-file") {
+        Some(name) => name.to_string(),
+        None => "./frens.yaml".to_string(),
+    };
+
+    let frens_file = parse_frens_file(frens_file_name).unwrap();
+    let schema_name = frens_file.schema_name();
+    let table_prefix = frens_file.table_prefix();
+    let tokens = frens_file.token_metadata();
+    let organizations = frens_file.all_organizations();
+    let organization = match options.get("dao") {
+        Some(dao_id) => organizations
+            .into_iter()
+            .find(|o| o.dao_id() == *dao_id)
+            .unwrap_or_else(|| panic!("Unknown DAO {}", dao_id)),
+        None => organizations
+            .into_iter()
+            .next()
+            .expect("No DAO in frens.yaml"),
+    };
+
+    Context {
+        organization,
+        schema_name,
+        table_prefix,
+        tokens,
+    }
+}
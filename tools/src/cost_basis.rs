@@ -0,0 +1,702 @@
+use crate::dates::{civil_from_days, date, days_from_civil};
+use crate::records::{normalize_address, TokenPrice, Transfer, REASON_GAS_BUY, REASON_GAS_REFUND};
+use bigdecimal::num_bigint::BigInt;
+use bigdecimal::{BigDecimal, Zero};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::str::FromStr;
+
+// Order in which the lots of an account are disposed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Method {
+    Fifo, // first in, first out
+    Lifo, // last in, first out
+    Hifo, // highest unit cost first
+}
+
+impl Method {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Method::Fifo => "fifo",
+            Method::Lifo => "lifo",
+            Method::Hifo => "hifo",
+        }
+    }
+}
+
+// Reporting period, in UTC days since the Unix epoch
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Period {
+    Day,
+    Month,
+    Year,
+}
+
+impl Period {
+    // First day of the period containing a day
+    fn start(&self, day: i64) -> i64 {
+        let (year, month, _) = civil_from_days(day);
+        match self {
+            Period::Day => day,
+            Period::Month => days_from_civil(year, month, 1),
+            Period::Year => days_from_civil(year, 1, 1),
+        }
+    }
+
+    // First day of the period following the one starting on a day
+    fn next(&self, start: i64) -> i64 {
+        let (year, month, _) = civil_from_days(start);
+        match self {
+            Period::Day => start + 1,
+            Period::Month if month == 12 => days_from_civil(year + 1, 1, 1),
+            Period::Month => days_from_civil(year, month + 1, 1),
+            Period::Year => days_from_civil(year + 1, 1, 1),
+        }
+    }
+}
+
+// Columns of the gains report, one row per period, account and token
+pub const GAINS_COLUMNS: [&str; 21] = [
+    "dao_id",
+    "period_start",
+    "period_end",
+    "account",
+    "account_name",
+    "token_address",
+    "symbol",
+    "method",
+    "opening_quantity",
+    "acquired_quantity",
+    "disposed_quantity",
+    "transferred_in_quantity",
+    "transferred_out_quantity",
+    "closing_quantity",
+    "disposed_cost_basis",
+    "proceeds",
+    "realized_gain",
+    "closing_cost_basis",
+    "closing_price",
+    "closing_value",
+    "unrealized_gain",
+];
+
+// Columns of the lots report, one row per open lot after the last transfer
+pub const LOTS_COLUMNS: [&str; 9] = [
+    "dao_id",
+    "account",
+    "account_name",
+    "token_address",
+    "symbol",
+    "acquired_date",
+    "quantity",
+    "unit_cost",
+    "cost_basis",
+];
+
+// USD prices of tokens over time, from the token_prices table and the value_usd of the transfers
+#[derive(Default)]
+pub struct PriceTable {
+    prices: HashMap<String, Vec<(i64, BigDecimal)>>,
+}
+
+impl PriceTable {
+    pub fn add(&mut self, token_address: &str, timestamp: i64, price: BigDecimal) {
+        self.prices
+            .entry(token_address.to_string())
+            .or_default()
+            .push((timestamp, price));
+    }
+
+    pub fn add_token_prices(&mut self, prices: &[TokenPrice]) {
+        for p in prices.iter() {
+            if let Ok(price) = BigDecimal::from_str(&p.price) {
+                self.add(&normalize_address(&p.token_address), p.timestamp, price);
+            }
+        }
+    }
+
+    // Last price at or before a timestamp
+    pub fn price_at(&self, token_address: &str, timestamp: i64) -> Option<BigDecimal> {
+        let prices = self.prices.get(token_address)?;
+        match prices.partition_point(|(t, _)| *t <= timestamp) {
+            0 => None,
+            index => Some(prices[index - 1].1.clone()),
+        }
+    }
+
+    fn sort(&mut self) {
+        for prices in self.prices.values_mut() {
+            prices.sort_by_key(|(t, _)| *t);
+        }
+    }
+}
+
+// Quantity of a token acquired at the same time and unit cost
+#[derive(Debug, Clone)]
+pub struct Lot {
+    pub acquired_at: i64,
+    pub quantity: BigDecimal,
+    pub unit_cost: BigDecimal,
+}
+
+// Movements of an account and token during a period
+#[derive(Default)]
+struct Flows {
+    acquired: BigDecimal,
+    disposed: BigDecimal,
+    transferred_in: BigDecimal,
+    transferred_out: BigDecimal,
+    disposed_cost_basis: BigDecimal,
+    proceeds: BigDecimal,
+}
+
+// Cost-basis lots of the treasury accounts of a DAO.  Tokens received from outside the DAO open a lot at their
+// USD price, tokens sent outside the DAO dispose of lots in the order of the method and realize a gain, and
+// transfers between treasury accounts move lots without realizing a gain.  Gas refunds reduce the gas bought
+// in their transaction.  Quantities sent beyond the open lots, and tokens without a price, have a zero cost
+// basis.  NFTs and tokens without decimals are skipped.
+pub struct CostBasis {
+    dao_id: String,
+    method: Method,
+    period: Period,
+    accounts: HashMap<String, String>, // treasury address => name
+    tokens: HashMap<String, (String, u32)>, // token address => (symbol, decimals)
+    prices: PriceTable,
+    lots: BTreeMap<(String, String), Vec<Lot>>, // (account, token) => open lots in acquisition order
+}
+
+impl CostBasis {
+    pub fn new(
+        dao_id: &str,
+        method: Method,
+        period: Period,
+        tokens: HashMap<String, (String, u32)>,
+        prices: PriceTable,
+    ) -> CostBasis {
+        let mut tokens = tokens;
+        tokens.insert("ETH".to_string(), ("ETH".to_string(), 18));
+
+        CostBasis {
+            dao_id: dao_id.to_string(),
+            method,
+            period,
+            accounts: HashMap::new(),
+            tokens,
+            prices,
+            lots: BTreeMap::new(),
+        }
+    }
+
+    pub fn add_account(&mut self, address: &str, name: &str) {
+        self.accounts
+            .insert(normalize_address(address), name.to_string());
+    }
+
+    // Replay the transfers, returning the rows of the gains report
+    pub fn gains(&mut self, transfers: &[Transfer]) -> Vec<Vec<String>> {
+        let transfers: Vec<(&Transfer, BigDecimal)> = transfers
+            .iter()
+            .filter(|t| t.token_id.is_empty())
+            .filter_map(|t| Some((t, self.quantity(t)?)))
+            .collect();
+
+        // Transfers priced by the substream imply a price for the other transfers of the token
+        for (t, quantity) in transfers.iter() {
+            if let Ok(value_usd) = BigDecimal::from_str(&t.value_usd) {
+                if !quantity.is_zero() {
+                    self.prices
+                        .add(&t.token_address, t.timestamp, value_usd / quantity);
+                }
+            }
+        }
+        self.prices.sort();
+
+        // A gas refund is a negative fee, netted against the gas its account bought in the same transaction
+        let gas_buys: BTreeSet<(&str, &str)> = transfers
+            .iter()
+            .filter(|(t, _)| t.reason == Some(REASON_GAS_BUY))
+            .map(|(t, _)| (t.hash.as_str(), t.from.as_str()))
+            .collect();
+        let mut refunds: HashMap<(&str, &str), BigDecimal> = HashMap::new();
+        for (t, quantity) in transfers.iter() {
+            let key = (t.hash.as_str(), t.to.as_str());
+            if t.reason == Some(REASON_GAS_REFUND) && gas_buys.contains(&key) {
+                *refunds.entry(key).or_insert_with(BigDecimal::zero) += quantity;
+            }
+        }
+
+        let mut rows: Vec<Vec<String>> = Vec::new();
+        let mut start = match transfers.first() {
+            Some((t, _)) => self.period.start(t.timestamp / 86400),
+            None => return rows,
+        };
+        let mut next = self.period.next(start);
+        let mut flows: BTreeMap<(String, String), Flows> = BTreeMap::new();
+
+        for (t, quantity) in transfers.iter() {
+            let refund_key = (t.hash.as_str(), t.to.as_str());
+            if t.reason == Some(REASON_GAS_REFUND) && refunds.contains_key(&refund_key) {
+                continue;
+            }
+            let refund = match t.reason {
+                Some(REASON_GAS_BUY) => refunds.get(&(t.hash.as_str(), t.from.as_str())),
+                _ => None,
+            };
+            while t.timestamp / 86400 >= next {
+                rows.extend(self.close_period(start, next, &mut flows));
+                start = next;
+                next = self.period.next(start);
+            }
+            let price = self.price(t, quantity);
+            match refund {
+                Some(refund) => self.apply(t, &(quantity - refund), price, &mut flows),
+                None => self.apply(t, quantity, price, &mut flows),
+            }
+        }
+        rows.extend(self.close_period(start, next, &mut flows));
+
+        rows
+    }
+
+    // Rows of the lots report, for the lots left open by gains()
+    pub fn lots(&self) -> Vec<Vec<String>> {
+        self.lots
+            .iter()
+            .flat_map(|((account, token_address), lots)| {
+                lots.iter().map(move |lot| {
+                    vec![
+                        self.dao_id.to_string(),
+                        account.to_string(),
+                        self.account_name(account),
+                        token_address.to_string(),
+                        self.tokens[token_address].0.to_string(),
+                        date(lot.acquired_at),
+                        quantity_string(&lot.quantity),
+                        usd_string(&lot.unit_cost),
+                        usd_string(&(&lot.quantity * &lot.unit_cost)),
+                    ]
+                })
+            })
+            .collect()
+    }
+
+    fn quantity(&self, t: &Transfer) -> Option<BigDecimal> {
+        let (_, decimals) = self.tokens.get(&t.token_address)?;
+        let value = BigInt::from_str(&t.value).ok()?;
+        Some(BigDecimal::new(value, *decimals as i64))
+    }
+
+    // USD price of a transfer's token, from its value_usd or else the price table
+    fn price(&self, t: &Transfer, quantity: &BigDecimal) -> Option<BigDecimal> {
+        match BigDecimal::from_str(&t.value_usd) {
+            Ok(value_usd) if !quantity.is_zero() => Some(value_usd / quantity),
+            _ => self.prices.price_at(&t.token_address, t.timestamp),
+        }
+    }
+
+    fn apply(
+        &mut self,
+        t: &Transfer,
+        quantity: &BigDecimal,
+        price: Option<BigDecimal>,
+        flows: &mut BTreeMap<(String, String), Flows>,
+    ) {
+        let key = |account: &String| (account.to_string(), t.token_address.to_string());
+
+        match (
+            self.accounts.contains_key(&t.from),
+            self.accounts.contains_key(&t.to),
+        ) {
+            (true, true) => {
+                let moved = self.take(&key(&t.from), quantity, t.timestamp);
+                let lots = self.lots.entry(key(&t.to)).or_default();
+                lots.extend(moved);
+                lots.sort_by_key(|lot| lot.acquired_at);
+
+                flows.entry(key(&t.from)).or_default().transferred_out += quantity;
+                flows.entry(key(&t.to)).or_default().transferred_in += quantity;
+            }
+            (false, true) => {
+                self.lots.entry(key(&t.to)).or_default().push(Lot {
+                    acquired_at: t.timestamp,
+                    quantity: quantity.clone(),
+                    unit_cost: price.unwrap_or_else(BigDecimal::zero),
+                });
+
+                flows.entry(key(&t.to)).or_default().acquired += quantity;
+            }
+            (true, false) => {
+                let taken = self.take(&key(&t.from), quantity, t.timestamp);
+                let cost_basis: BigDecimal =
+                    taken.iter().map(|lot| &lot.quantity * &lot.unit_cost).sum();
+
+                let f = flows.entry(key(&t.from)).or_default();
+                f.disposed += quantity;
+                f.disposed_cost_basis += cost_basis;
+                if let Some(price) = price {
+                    f.proceeds += price * quantity;
+                }
+            }
+            (false, false) => {}
+        }
+    }
+
+    // Remove a quantity from the open lots of an account and token, in the order of the method
+    fn take(&mut self, key: &(String, String), quantity: &BigDecimal, timestamp: i64) -> Vec<Lot> {
+        let method = self.method;
+        let lots = self.lots.entry(key.clone()).or_default();
+        let mut taken: Vec<Lot> = Vec::new();
+        let mut remaining = quantity.clone();
+
+        while remaining > BigDecimal::zero() {
+            if lots.is_empty() {
+                taken.push(Lot {
+                    acquired_at: timestamp,
+                    quantity: remaining,
+                    unit_cost: BigDecimal::zero(),
+                });
+                break;
+            }
+
+            // HIFO takes the earliest of the lots with the highest unit cost
+            let index = match method {
+                Method::Fifo => 0,
+                Method::Lifo => lots.len() - 1,
+                Method::Hifo => (0..lots.len())
+                    .rev()
+                    .max_by(|a, b| lots[*a].unit_cost.cmp(&lots[*b].unit_cost))
+                    .unwrap(),
+            };
+
+            if lots[index].quantity > remaining {
+                lots[index].quantity -= &remaining;
+                taken.push(Lot {
+                    acquired_at: lots[index].acquired_at,
+                    quantity: remaining,
+                    unit_cost: lots[index].unit_cost.clone(),
+                });
+                break;
+            }
+            let lot = lots.remove(index);
+            remaining -= &lot.quantity;
+            taken.push(lot);
+        }
+
+        taken
+    }
+
+    // Rows of the accounts and tokens with movements during the period or open lots at its end
+    fn close_period(
+        &self,
+        start: i64,
+        next: i64,
+        flows: &mut BTreeMap<(String, String), Flows>,
+    ) -> Vec<Vec<String>> {
+        let keys: BTreeSet<(String, String)> = self
+            .lots
+            .iter()
+            .filter(|(_, lots)| !lots.is_empty())
+            .map(|(key, _)| key.clone())
+            .chain(flows.keys().cloned())
+            .collect();
+        let period_end = next * 86400 - 1;
+
+        keys.into_iter()
+            .map(|key| {
+                let (account, token_address) = &key;
+                let f = flows.remove(&key).unwrap_or_default();
+                let lots: &[Lot] = self.lots.get(&key).map(|l| &l[..]).unwrap_or(&[]);
+
+                let closing_quantity: BigDecimal =
+                    lots.iter().map(|lot| lot.quantity.clone()).sum();
+                let closing_cost_basis: BigDecimal =
+                    lots.iter().map(|lot| &lot.quantity * &lot.unit_cost).sum();
+                let opening_quantity = &closing_quantity - &f.acquired + &f.disposed
+                    - &f.transferred_in
+                    + &f.transferred_out;
+                let realized_gain = &f.proceeds - &f.disposed_cost_basis;
+
+                let closing_price = self.prices.price_at(token_address, period_end);
+                let closing_value = closing_price.as_ref().map(|p| p * &closing_quantity);
+                let unrealized_gain = closing_value.as_ref().map(|v| v - &closing_cost_basis);
+
+                vec![
+                    self.dao_id.to_string(),
+                    date(start * 86400),
+                    date(period_end),
+                    account.to_string(),
+                    self.account_name(account),
+                    token_address.to_string(),
+                    self.tokens[token_address].0.to_string(),
+                    self.method.name().to_string(),
+                    quantity_string(&opening_quantity),
+                    quantity_string(&f.acquired),
+                    quantity_string(&f.disposed),
+                    quantity_string(&f.transferred_in),
+                    quantity_string(&f.transferred_out),
+                    quantity_string(&closing_quantity),
+                    usd_string(&f.disposed_cost_basis),
+                    usd_string(&f.proceeds),
+                    usd_string(&realized_gain),
+                    usd_string(&closing_cost_basis),
+                    closing_price.as_ref().map(usd_string).unwrap_or_default(),
+                    closing_value.as_ref().map(usd_string).unwrap_or_default(),
+                    unrealized_gain.as_ref().map(usd_string).unwrap_or_default(),
+                ]
+            })
+            .collect()
+    }
+
+    fn account_name(&self, address: &str) -> String {
+        self.accounts.get(address).cloned().unwrap_or_default()
+    }
+}
+
+fn quantity_string(quantity: &BigDecimal) -> String {
+    quantity.normalized().to_string()
+}
+
+// USD amounts are rounded to 6 decimals
+fn usd_string(amount: &BigDecimal) -> String {
+    amount.round(6).with_scale(6).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TREASURY: &str = "000000000000000000000000000000000000000a";
+    const VAULT: &str = "000000000000000000000000000000000000000b";
+    const OUTSIDE: &str = "000000000000000000000000000000000000000c";
+    const TOKEN: &str = "00000000000000000000000000000000000000d0";
+
+    // Column indexes of the gains report
+    const PERIOD_START: usize = 1;
+    const PERIOD_END: usize = 2;
+    const ACCOUNT: usize = 3;
+    const OPENING_QUANTITY: usize = 8;
+    const DISPOSED_QUANTITY: usize = 10;
+    const TRANSFERRED_IN_QUANTITY: usize = 11;
+    const TRANSFERRED_OUT_QUANTITY: usize = 12;
+    const CLOSING_QUANTITY: usize = 13;
+    const DISPOSED_COST_BASIS: usize = 14;
+    const PROCEEDS: usize = 15;
+    const REALIZED_GAIN: usize = 16;
+    const CLOSING_COST_BASIS: usize = 17;
+    const UNREALIZED_GAIN: usize = 20;
+
+    fn timestamp(year: i64, month: u32, day: u32) -> i64 {
+        days_from_civil(year, month, day) * 86400 + 3600
+    }
+
+    fn transfer(timestamp: i64, from: &str, to: &str, value: &str, value_usd: &str) -> Transfer {
+        Transfer {
+            hash: format!("0x{}", timestamp),
+            block_number: 0,
+            timestamp,
+            ordinal: 0,
+            from: from.to_string(),
+            to: to.to_string(),
+            token_address: TOKEN.to_string(),
+            token_id: "".to_string(),
+            value: value.to_string(),
+            reason: None,
+            value_usd: value_usd.to_string(),
+        }
+    }
+
+    fn cost_basis(method: Method, period: Period) -> CostBasis {
+        let tokens = HashMap::from([(TOKEN.to_string(), ("TKN".to_string(), 0))]);
+        let mut cost_basis = CostBasis::new("dao", method, period, tokens, PriceTable::default());
+        cost_basis.add_account(TREASURY, "Treasury");
+        cost_basis.add_account(VAULT, "Vault");
+        cost_basis
+    }
+
+    // Buys 10 at $1, 10 at $3 and 10 at $2, then sells 15 at $4
+    fn buys_and_sell() -> Vec<Transfer> {
+        vec![
+            transfer(timestamp(2023, 1, 2), OUTSIDE, TREASURY, "10", "10"),
+            transfer(timestamp(2023, 1, 3), OUTSIDE, TREASURY, "10", "30"),
+            transfer(timestamp(2023, 1, 4), OUTSIDE, TREASURY, "10", "20"),
+            transfer(timestamp(2023, 1, 5), TREASURY, OUTSIDE, "15", "60"),
+        ]
+    }
+
+    #[test]
+    fn methods_realize_gains_in_their_order() {
+        for (method, disposed_cost_basis, realized_gain, closing_cost_basis, unrealized_gain) in [
+            (Method::Fifo, "25", "35", "35", "25"),
+            (Method::Lifo, "35", "25", "25", "35"),
+            (Method::Hifo, "40", "20", "20", "40"),
+        ] {
+            let rows = cost_basis(method, Period::Month).gains(&buys_and_sell());
+            assert_eq!(rows.len(), 1);
+            let row = &rows[0];
+            assert_eq!(row[PERIOD_START], "2023-01-01");
+            assert_eq!(row[PERIOD_END], "2023-01-31");
+            assert_eq!(row[OPENING_QUANTITY], "0");
+            assert_eq!(row[DISPOSED_QUANTITY], "15");
+            assert_eq!(row[CLOSING_QUANTITY], "15");
+            assert_eq!(row[PROCEEDS], "60.000000");
+            assert_eq!(
+                row[DISPOSED_COST_BASIS],
+                format!("{}.000000", disposed_cost_basis)
+            );
+            assert_eq!(row[REALIZED_GAIN], format!("{}.000000", realized_gain));
+            assert_eq!(
+                row[CLOSING_COST_BASIS],
+                format!("{}.000000", closing_cost_basis)
+            );
+            assert_eq!(row[UNREALIZED_GAIN], format!("{}.000000", unrealized_gain));
+        }
+    }
+
+    #[test]
+    fn hifo_takes_the_earliest_of_the_highest_unit_cost() {
+        let mut cost_basis = cost_basis(Method::Hifo, Period::Month);
+        cost_basis.gains(&[
+            transfer(timestamp(2023, 1, 2), OUTSIDE, TREASURY, "5", "10"),
+            transfer(timestamp(2023, 1, 3), OUTSIDE, TREASURY, "5", "10"),
+            transfer(timestamp(2023, 1, 4), OUTSIDE, TREASURY, "5", "5"),
+            transfer(timestamp(2023, 1, 5), TREASURY, OUTSIDE, "5", "20"),
+        ]);
+
+        let lots = cost_basis.lots();
+        assert_eq!(lots.len(), 2);
+        assert_eq!(lots[0][5], "2023-01-03");
+        assert_eq!(lots[0][7], "2.000000");
+        assert_eq!(lots[1][5], "2023-01-04");
+        assert_eq!(lots[1][7], "1.000000");
+    }
+
+    #[test]
+    fn treasury_transfers_move_lots_without_a_gain() {
+        let mut cost_basis = cost_basis(Method::Fifo, Period::Month);
+        let rows = cost_basis.gains(&[
+            transfer(timestamp(2023, 1, 2), OUTSIDE, TREASURY, "10", "10"),
+            transfer(timestamp(2023, 1, 3), TREASURY, VAULT, "4", "20"),
+        ]);
+
+        assert_eq!(rows.len(), 2);
+        let (treasury, vault) = (&rows[0], &rows[1]);
+        assert_eq!(treasury[ACCOUNT], TREASURY);
+        assert_eq!(treasury[TRANSFERRED_OUT_QUANTITY], "4");
+        assert_eq!(treasury[CLOSING_QUANTITY], "6");
+        assert_eq!(treasury[REALIZED_GAIN], "0.000000");
+        assert_eq!(vault[ACCOUNT], VAULT);
+        assert_eq!(vault[TRANSFERRED_IN_QUANTITY], "4");
+        assert_eq!(vault[CLOSING_QUANTITY], "4");
+        assert_eq!(vault[REALIZED_GAIN], "0.000000");
+
+        // The moved lot keeps its acquisition date and unit cost
+        let lots = cost_basis.lots();
+        assert_eq!(lots[1][1], VAULT);
+        assert_eq!(lots[1][5], "2023-01-02");
+        assert_eq!(lots[1][7], "1.000000");
+    }
+
+    #[test]
+    fn gains_roll_over_months_and_years() {
+        let rows = cost_basis(Method::Fifo, Period::Month).gains(&[
+            transfer(timestamp(2022, 12, 31), OUTSIDE, TREASURY, "10", "10"),
+            transfer(timestamp(2023, 2, 1), TREASURY, OUTSIDE, "4", "8"),
+        ]);
+
+        let periods: Vec<(&str, &str)> = rows
+            .iter()
+            .map(|row| (row[PERIOD_START].as_str(), row[PERIOD_END].as_str()))
+            .collect();
+        assert_eq!(
+            periods,
+            [
+                ("2022-12-01", "2022-12-31"),
+                ("2023-01-01", "2023-01-31"),
+                ("2023-02-01", "2023-02-28"),
+            ]
+        );
+        assert_eq!(rows[1][OPENING_QUANTITY], "10");
+        assert_eq!(rows[1][CLOSING_QUANTITY], "10");
+        assert_eq!(rows[2][OPENING_QUANTITY], "10");
+        assert_eq!(rows[2][REALIZED_GAIN], "4.000000");
+
+        let rows = cost_basis(Method::Fifo, Period::Year).gains(&[
+            transfer(timestamp(2022, 12, 31), OUTSIDE, TREASURY, "10", "10"),
+            transfer(timestamp(2023, 1, 1), TREASURY, OUTSIDE, "4", "8"),
+        ]);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0][PERIOD_END], "2022-12-31");
+        assert_eq!(rows[1][PERIOD_START], "2023-01-01");
+        assert_eq!(rows[1][PERIOD_END], "2023-12-31");
+    }
+
+    #[test]
+    fn periods_start_and_end_on_calendar_boundaries() {
+        let day = |year, month, day| days_from_civil(year, month, day);
+
+        assert_eq!(Period::Day.start(day(2023, 3, 15)), day(2023, 3, 15));
+        assert_eq!(Period::Month.start(day(2023, 3, 15)), day(2023, 3, 1));
+        assert_eq!(Period::Year.start(day(2023, 3, 15)), day(2023, 1, 1));
+
+        assert_eq!(Period::Day.next(day(2023, 12, 31)), day(2024, 1, 1));
+        assert_eq!(Period::Month.next(day(2024, 1, 1)), day(2024, 2, 1));
+        assert_eq!(Period::Month.next(day(2024, 2, 1)), day(2024, 3, 1));
+        assert_eq!(Period::Month.next(day(2023, 12, 1)), day(2024, 1, 1));
+        assert_eq!(Period::Year.next(day(2023, 1, 1)), day(2024, 1, 1));
+    }
+
+    #[test]
+    fn take_beyond_the_open_lots_has_a_zero_cost_basis() {
+        let mut cost_basis = cost_basis(Method::Fifo, Period::Month);
+        let key = (TREASURY.to_string(), TOKEN.to_string());
+        cost_basis.lots.insert(
+            key.clone(),
+            vec![Lot {
+                acquired_at: timestamp(2023, 1, 2),
+                quantity: BigDecimal::from(5),
+                unit_cost: BigDecimal::from(2),
+            }],
+        );
+
+        let taken = cost_basis.take(&key, &BigDecimal::from(8), timestamp(2023, 1, 5));
+        assert_eq!(taken.len(), 2);
+        assert_eq!(taken[0].quantity, BigDecimal::from(5));
+        assert_eq!(taken[0].unit_cost, BigDecimal::from(2));
+        assert_eq!(taken[1].quantity, BigDecimal::from(3));
+        assert_eq!(taken[1].unit_cost, BigDecimal::zero());
+        assert_eq!(taken[1].acquired_at, timestamp(2023, 1, 5));
+        assert!(cost_basis.lots[&key].is_empty());
+
+        let rows = cost_basis.gains(&[
+            transfer(timestamp(2023, 1, 2), OUTSIDE, TREASURY, "5", "10"),
+            transfer(timestamp(2023, 1, 5), TREASURY, OUTSIDE, "8", "24"),
+        ]);
+        assert_eq!(rows[0][DISPOSED_COST_BASIS], "10.000000");
+        assert_eq!(rows[0][REALIZED_GAIN], "14.000000");
+        assert_eq!(rows[0][CLOSING_QUANTITY], "0");
+    }
+
+    #[test]
+    fn gas_refunds_reduce_the_gas_spent() {
+        let mut prices = PriceTable::default();
+        prices.add("ETH", timestamp(2023, 1, 1), BigDecimal::from(1000));
+        let mut cost_basis =
+            CostBasis::new("dao", Method::Fifo, Period::Month, HashMap::new(), prices);
+        cost_basis.add_account(TREASURY, "Treasury");
+
+        let eth = |from: &str, to: &str, value: &str, reason: Option<i32>| Transfer {
+            token_address: "ETH".to_string(),
+            reason,
+            ..transfer(timestamp(2023, 1, 2), from, to, value, "")
+        };
+        let rows = cost_basis.gains(&[
+            eth(OUTSIDE, TREASURY, "2000000000000000000", None),
+            eth(TREASURY, "", "1000000000000000000", Some(REASON_GAS_BUY)),
+            eth("", TREASURY, "250000000000000000", Some(REASON_GAS_REFUND)),
+        ]);
+
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0][DISPOSED_QUANTITY], "0.75");
+        assert_eq!(rows[0][CLOSING_QUANTITY], "1.25");
+        assert_eq!(rows[0][PROCEEDS], "750.000000");
+    }
+}
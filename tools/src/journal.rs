@@ -0,0 +1,520 @@
+use crate::dates::date;
+use crate::frensfile::{AddressLabel, FrensFile, TokenMetadata};
+use crate::records::{
+    normalize_address, Account, Transfer, REASON_GAS_BUY, REASON_GAS_REFUND,
+    REASON_REWARD_MINE_BLOCK, REASON_REWARD_MINE_UNCLE, REASON_WITHDRAWAL,
+};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::io::Write;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Beancount,
+    Ledger,
+}
+
+// Plain-text accounting journal of the transfers of a DAO: one transaction per transaction hash, with a posting
+// on each side of every transfer
+pub struct Journal {
+    format: Format,
+    accounts: HashMap<String, String>, // own address => ledger account
+    labels: HashMap<String, String>,   // counterparty address => account component
+    tokens: HashMap<String, (String, Option<u32>)>, // token address => (commodity, decimals)
+}
+
+struct Posting {
+    account: String,
+    amount: String,
+    commodity: String,
+}
+
+impl Journal {
+    // Ledger accounts from the treasury accounts and issued tokens of frens.yaml, counterparties from its labels
+    pub fn new(organization: &FrensFile, tokens: &[TokenMetadata], format: Format) -> Journal {
+        let mut journal = Journal {
+            format,
+            accounts: HashMap::new(),
+            labels: HashMap::new(),
+            tokens: HashMap::new(),
+        };
+
+        for label in organization.address_labels().iter() {
+            journal.add_label(label);
+        }
+        for t in organization.tokens_issued.iter() {
+            journal.accounts.insert(
+                normalize_address(&t.address),
+                format!("Assets:Issued:{}", account_component(&t.name)),
+            );
+        }
+        for a in organization.treasury_accounts.iter() {
+            journal.add_account(&Account {
+                address: a.address.to_string(),
+                name: a.name.to_string(),
+                group: a.group.clone().unwrap_or_default(),
+            });
+        }
+
+        // Later entries take precedence, as in the tokens table
+        let mut commodities: BTreeSet<String> = BTreeSet::from(["ETH".to_string()]);
+        journal
+            .tokens
+            .insert("ETH".to_string(), ("ETH".to_string(), Some(18)));
+        let mut metadata: BTreeMap<String, (Option<String>, Option<u32>)> = BTreeMap::new();
+        for t in tokens.iter() {
+            let entry = metadata
+                .entry(normalize_address(&t.address))
+                .or_insert((None, None));
+            if t.symbol.is_some() {
+                entry.0 = t.symbol.clone();
+            }
+            if t.decimals.is_some() {
+                entry.1 = t.decimals;
+            }
+        }
+        for (address, (symbol, decimals)) in metadata {
+            // Tokens without a valid and unique symbol are named after their address
+            let commodity = match symbol.as_ref().and_then(|s| commodity_name(s)) {
+                Some(c) if !commodities.contains(&c) => c,
+                _ => fallback_commodity(&address),
+            };
+            commodities.insert(commodity.to_string());
+            journal.tokens.insert(address, (commodity, decimals));
+        }
+
+        journal
+    }
+
+    // Accounts of the database, which include the discovered accounts
+    pub fn add_account(&mut self, account: &Account) {
+        let name = if account.group.is_empty() {
+            format!("Assets:Treasury:{}", account_component(&account.name))
+        } else {
+            format!(
+                "Assets:Treasury:{}:{}",
+                account_component(&account.group),
+                account_component(&account.name)
+            )
+        };
+        self.accounts
+            .insert(normalize_address(&account.address), name);
+    }
+
+    fn add_label(&mut self, label: &AddressLabel) {
+        self.labels.insert(
+            normalize_address(&label.address),
+            account_component(&label.name),
+        );
+    }
+
+    pub fn write(&self, transfers: &[Transfer], out: &mut dyn Write) -> std::io::Result<()> {
+        // Transactions in the order of their first transfer
+        let mut hashes: Vec<&String> = Vec::new();
+        let mut by_hash: HashMap<&String, Vec<&Transfer>> = HashMap::new();
+        for t in transfers.iter().filter(|t| t.token_id.is_empty()) {
+            if !by_hash.contains_key(&t.hash) {
+                hashes.push(&t.hash);
+            }
+            by_hash.entry(&t.hash).or_default().push(t);
+        }
+
+        let mut transactions: Vec<(&Transfer, Vec<Posting>)> = Vec::new();
+        for hash in hashes.iter() {
+            let group = &by_hash[hash];
+            let postings: Vec<Posting> = group.iter().flat_map(|t| self.postings(t)).collect();
+            transactions.push((group[0], postings));
+        }
+
+        let first_date = match transactions.first() {
+            Some((t, _)) => date(t.timestamp),
+            None => return Ok(()),
+        };
+        let accounts: BTreeSet<&String> = transactions
+            .iter()
+            .flat_map(|(_, postings)| postings.iter().map(|p| &p.account))
+            .collect();
+        let commodities: BTreeSet<(&String, &String)> = self
+            .tokens
+            .iter()
+            .filter(|(address, _)| *address != "ETH")
+            .filter(|(address, _)| transfers.iter().any(|t| t.token_address == **address))
+            .map(|(address, (commodity, _))| (commodity, address))
+            .collect();
+
+        for (commodity, address) in commodities.iter() {
+            match self.format {
+                Format::Beancount => writeln!(
+                    out,
+                    "{} commodity {}\n  address: \"{}\"",
+                    first_date, commodity, address
+                )?,
+                Format::Ledger => writeln!(
+                    out,
+                    "commodity {}\n    note {}",
+                    self.commodity(commodity),
+                    address
+                )?,
+            }
+        }
+        writeln!(out)?;
+        for account in accounts.iter() {
+            match self.format {
+                Format::Beancount => writeln!(out, "{} open {}", first_date, account)?,
+                Format::Ledger => writeln!(out, "account {}", account)?,
+            }
+        }
+
+        for (t, postings) in transactions.iter() {
+            let narration = match t.reason {
+                Some(REASON_REWARD_MINE_BLOCK) | Some(REASON_REWARD_MINE_UNCLE) => "Block reward",
+                Some(REASON_WITHDRAWAL) => "Withdrawal",
+                _ => "Transaction",
+            };
+            writeln!(out)?;
+            match self.format {
+                Format::Beancount => {
+                    writeln!(out, "{} * \"{}\"", date(t.timestamp), narration)?;
+                    writeln!(out, "  hash: \"0x{}\"", t.hash)?;
+                    writeln!(out, "  block_number: {}", t.block_number)?;
+                }
+                Format::Ledger => {
+                    writeln!(out, "{} * {}", date(t.timestamp), narration)?;
+                    writeln!(out, "    ; hash: 0x{}", t.hash)?;
+                    writeln!(out, "    ; block_number: {}", t.block_number)?;
+                }
+            }
+            for p in postings.iter() {
+                writeln!(
+                    out,
+                    "  {:<60} {:>24} {}",
+                    p.account,
+                    p.amount,
+                    self.commodity(&p.commodity)
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // The receiving side of a transfer is credited and the sending side debited
+    fn postings(&self, t: &Transfer) -> Vec<Posting> {
+        let (commodity, decimals) = match self.tokens.get(&t.token_address) {
+            Some((commodity, decimals)) => (commodity.to_string(), *decimals),
+            None => (fallback_commodity(&t.token_address), None),
+        };
+        let amount = decimal_amount(&t.value, decimals.unwrap_or(0));
+
+        vec![
+            Posting {
+                account: self.account(&t.to, &t.from, t.reason, true),
+                amount: amount.to_string(),
+                commodity: commodity.to_string(),
+            },
+            Posting {
+                account: self.account(&t.from, &t.to, t.reason, false),
+                amount: format!("-{}", amount),
+                commodity,
+            },
+        ]
+    }
+
+    fn account(
+        &self,
+        address: &String,
+        other: &String,
+        reason: Option<i32>,
+        incoming: bool,
+    ) -> String {
+        if let Some(account) = self.accounts.get(address) {
+            return account.to_string();
+        }
+
+        // ETH created or destroyed by the protocol
+        if address.is_empty() {
+            return match reason {
+                Some(REASON_GAS_BUY) | Some(REASON_GAS_REFUND) => "Expenses:Gas".to_string(),
+                Some(REASON_REWARD_MINE_BLOCK) | Some(REASON_REWARD_MINE_UNCLE) => {
+                    "Income:BlockRewards".to_string()
+                }
+                Some(REASON_WITHDRAWAL) => "Assets:Staking:BeaconChain".to_string(),
+                _ => "Equity:Adjustments".to_string(),
+            };
+        }
+
+        // Counterparty: the other side is ours, so money coming from it is income
+        let root = if incoming || !self.accounts.contains_key(other) {
+            "Expenses"
+        } else {
+            "Income"
+        };
+        let name = match self.labels.get(address) {
+            Some(name) => name.to_string(),
+            None => "Unknown".to_string(),
+        };
+        format!("{}:External:{}", root, name)
+    }
+
+    // Ledger quotes commodities which are not only letters
+    fn commodity(&self, commodity: &String) -> String {
+        match self.format {
+            Format::Ledger if !commodity.chars().all(|c| c.is_ascii_alphabetic()) => {
+                format!("\"{}\"", commodity)
+            }
+            _ => commodity.to_string(),
+        }
+    }
+}
+
+// Beancount account components start with a capital letter or digit, followed by letters, digits or dashes
+fn account_component(name: &str) -> String {
+    let words: Vec<String> = name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| {
+            let mut chars = w.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => "".to_string(),
+            }
+        })
+        .collect();
+    if words.is_empty() {
+        return "Unnamed".to_string();
+    }
+    words.join("-")
+}
+
+// Beancount commodities are up to 24 capital letters, digits or '._- and start with a letter
+fn commodity_name(symbol: &str) -> Option<String> {
+    let commodity = symbol.trim().to_uppercase();
+    let valid = commodity.len() <= 24
+        && commodity.starts_with(|c: char| c.is_ascii_uppercase())
+        && commodity.ends_with(|c: char| c.is_ascii_uppercase() || c.is_ascii_digit())
+        && commodity
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || "'._-".contains(c));
+    if valid {
+        Some(commodity)
+    } else {
+        None
+    }
+}
+
+fn fallback_commodity(address: &str) -> String {
+    format!("T{}", &address[..address.len().min(8)].to_uppercase())
+}
+
+// Insert the decimal point into a base-10 integer string
+fn decimal_amount(value: &str, decimals: u32) -> String {
+    let decimals = decimals as usize;
+    if decimals == 0 {
+        return value.to_string();
+    }
+    let padded = format!("{:0>width$}", value, width = decimals + 1);
+    let (units, fraction) = padded.split_at(padded.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        units.to_string()
+    } else {
+        format!("{}.{}", units, fraction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TREASURY: &str = "000000000000000000000000000000000000000a";
+    const COUNTERPARTY: &str = "000000000000000000000000000000000000000c";
+    const UNLABELED: &str = "000000000000000000000000000000000000000e";
+    const TOKEN: &str = "00000000000000000000000000000000000000d0";
+
+    fn journal(format: Format) -> Journal {
+        Journal {
+            format,
+            accounts: HashMap::from([(TREASURY.to_string(), "Assets:Treasury:Main".to_string())]),
+            labels: HashMap::from([(COUNTERPARTY.to_string(), "Acme-Corp".to_string())]),
+            tokens: HashMap::from([
+                ("ETH".to_string(), ("ETH".to_string(), Some(18))),
+                (TOKEN.to_string(), ("USD-X".to_string(), Some(2))),
+            ]),
+        }
+    }
+
+    fn transfer(
+        from: &str,
+        to: &str,
+        token_address: &str,
+        value: &str,
+        reason: Option<i32>,
+    ) -> Transfer {
+        Transfer {
+            hash: "ab".to_string(),
+            block_number: 100,
+            timestamp: 1672621200, // 2023-01-02
+            ordinal: 0,
+            from: from.to_string(),
+            to: to.to_string(),
+            token_address: token_address.to_string(),
+            token_id: "".to_string(),
+            value: value.to_string(),
+            reason,
+            value_usd: "".to_string(),
+        }
+    }
+
+    #[test]
+    fn account_components_are_capitalized_words() {
+        assert_eq!(account_component("Main treasury"), "Main-Treasury");
+        assert_eq!(account_component("gnosis_safe (ops)"), "Gnosis-Safe-Ops");
+        assert_eq!(account_component("2023 grants"), "2023-Grants");
+        assert_eq!(account_component("àé -"), "Unnamed");
+    }
+
+    #[test]
+    fn commodity_names_follow_beancount_rules() {
+        assert_eq!(commodity_name(" usdc "), Some("USDC".to_string()));
+        assert_eq!(commodity_name("stETH"), Some("STETH".to_string()));
+        assert_eq!(commodity_name("a.b-3"), Some("A.B-3".to_string()));
+        assert_eq!(commodity_name("1INCH"), None);
+        assert_eq!(commodity_name("USD-"), None);
+        assert_eq!(commodity_name("USD$"), None);
+        assert_eq!(commodity_name(&"A".repeat(25)), None);
+        assert_eq!(fallback_commodity(TOKEN), "T00000000");
+    }
+
+    #[test]
+    fn decimal_amounts_insert_the_decimal_point() {
+        assert_eq!(decimal_amount("1500000000000000000", 18), "1.5");
+        assert_eq!(decimal_amount("5", 6), "0.000005");
+        assert_eq!(decimal_amount("1000000", 6), "1");
+        assert_eq!(decimal_amount("1234500", 4), "123.45");
+        assert_eq!(decimal_amount("0", 18), "0");
+        assert_eq!(decimal_amount("42", 0), "42");
+    }
+
+    #[test]
+    fn protocol_and_counterparty_accounts() {
+        let journal = journal(Format::Beancount);
+        let empty = "".to_string();
+        let treasury = TREASURY.to_string();
+        let counterparty = COUNTERPARTY.to_string();
+        let unlabeled = UNLABELED.to_string();
+
+        assert_eq!(
+            journal.account(&treasury, &empty, None, false),
+            "Assets:Treasury:Main"
+        );
+        assert_eq!(
+            journal.account(&empty, &treasury, Some(REASON_GAS_BUY), true),
+            "Expenses:Gas"
+        );
+        assert_eq!(
+            journal.account(&empty, &treasury, Some(REASON_GAS_REFUND), false),
+            "Expenses:Gas"
+        );
+        assert_eq!(
+            journal.account(&empty, &treasury, Some(REASON_REWARD_MINE_BLOCK), false),
+            "Income:BlockRewards"
+        );
+        assert_eq!(
+            journal.account(&empty, &treasury, Some(REASON_REWARD_MINE_UNCLE), false),
+            "Income:BlockRewards"
+        );
+        assert_eq!(
+            journal.account(&empty, &treasury, Some(REASON_WITHDRAWAL), false),
+            "Assets:Staking:BeaconChain"
+        );
+        assert_eq!(
+            journal.account(&empty, &treasury, None, false),
+            "Equity:Adjustments"
+        );
+
+        // Paying a counterparty is an expense, being paid by one is income
+        assert_eq!(
+            journal.account(&counterparty, &treasury, None, true),
+            "Expenses:External:Acme-Corp"
+        );
+        assert_eq!(
+            journal.account(&counterparty, &treasury, None, false),
+            "Income:External:Acme-Corp"
+        );
+        assert_eq!(
+            journal.account(&unlabeled, &counterparty, None, false),
+            "Expenses:External:Unknown"
+        );
+    }
+
+    fn transaction() -> Vec<Transfer> {
+        vec![
+            transfer(TREASURY, COUNTERPARTY, TOKEN, "150", None),
+            transfer(COUNTERPARTY, TREASURY, "ETH", "1000000000000000000", None),
+            transfer(TREASURY, "", "ETH", "21000000000000", Some(REASON_GAS_BUY)),
+        ]
+    }
+
+    fn output(format: Format) -> String {
+        let mut out: Vec<u8> = Vec::new();
+        journal(format).write(&transaction(), &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn beancount_output() {
+        let posting = |account: &str, amount: &str, commodity: &str| {
+            format!("  {:<60} {:>24} {}\n", account, amount, commodity)
+        };
+        let expected = [
+            "2023-01-02 commodity USD-X\n".to_string(),
+            format!("  address: \"{}\"\n", TOKEN),
+            "\n".to_string(),
+            "2023-01-02 open Assets:Treasury:Main\n".to_string(),
+            "2023-01-02 open Expenses:External:Acme-Corp\n".to_string(),
+            "2023-01-02 open Expenses:Gas\n".to_string(),
+            "2023-01-02 open Income:External:Acme-Corp\n".to_string(),
+            "\n".to_string(),
+            "2023-01-02 * \"Transaction\"\n".to_string(),
+            "  hash: \"0xab\"\n".to_string(),
+            "  block_number: 100\n".to_string(),
+            posting("Expenses:External:Acme-Corp", "1.5", "USD-X"),
+            posting("Assets:Treasury:Main", "-1.5", "USD-X"),
+            posting("Assets:Treasury:Main", "1", "ETH"),
+            posting("Income:External:Acme-Corp", "-1", "ETH"),
+            posting("Expenses:Gas", "0.000021", "ETH"),
+            posting("Assets:Treasury:Main", "-0.000021", "ETH"),
+        ]
+        .concat();
+
+        assert_eq!(output(Format::Beancount), expected);
+    }
+
+    #[test]
+    fn ledger_output() {
+        let posting = |account: &str, amount: &str, commodity: &str| {
+            format!("  {:<60} {:>24} {}\n", account, amount, commodity)
+        };
+        let expected = [
+            "commodity \"USD-X\"\n".to_string(),
+            format!("    note {}\n", TOKEN),
+            "\n".to_string(),
+            "account Assets:Treasury:Main\n".to_string(),
+            "account Expenses:External:Acme-Corp\n".to_string(),
+            "account Expenses:Gas\n".to_string(),
+            "account Income:External:Acme-Corp\n".to_string(),
+            "\n".to_string(),
+            "2023-01-02 * Transaction\n".to_string(),
+            "    ; hash: 0xab\n".to_string(),
+            "    ; block_number: 100\n".to_string(),
+            posting("Expenses:External:Acme-Corp", "1.5", "\"USD-X\""),
+            posting("Assets:Treasury:Main", "-1.5", "\"USD-X\""),
+            posting("Assets:Treasury:Main", "1", "ETH"),
+            posting("Income:External:Acme-Corp", "-1", "ETH"),
+            posting("Expenses:Gas", "0.000021", "ETH"),
+            posting("Assets:Treasury:Main", "-0.000021", "ETH"),
+        ]
+        .concat();
+
+        assert_eq!(output(Format::Ledger), expected);
+    }
+}
@@ -0,0 +1,9 @@
+pub mod cli;
+pub mod cost_basis;
+pub mod dates;
+#[path = "../../src/pb/frenscan.types.v1.rs"]
+pub mod frenscan;
+#[path = "../../src/frensfile/mod.rs"]
+pub mod frensfile;
+pub mod journal;
+pub mod records;
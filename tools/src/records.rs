@@ -0,0 +1,231 @@
+use crate::frenscan::{DiscoveredAccounts, Transfers};
+use postgres::{Client, NoTls};
+use prost::Message;
+
+// Reasons of the ETH balance changes, from sf.ethereum.type.v2.BalanceChange
+pub const REASON_REWARD_MINE_UNCLE: i32 = 1;
+pub const REASON_REWARD_MINE_BLOCK: i32 = 2;
+pub const REASON_GAS_BUY: i32 = 7;
+pub const REASON_GAS_REFUND: i32 = 9;
+pub const REASON_WITHDRAWAL: i32 = 16;
+
+// Token or ETH transfer of a DAO, read from the map_blocks output or the database
+#[derive(Debug, Clone)]
+pub struct Transfer {
+    pub hash: String,
+    pub block_number: u64,
+    pub timestamp: i64,
+    pub ordinal: u64,
+    pub from: String,          // empty for ETH minted, refunded or withdrawn
+    pub to: String,            // empty for ETH burned or paid as gas
+    pub token_address: String, // ETH for value transfers
+    pub token_id: String,
+    pub value: String,       // base-10 string in the smallest unit of the token
+    pub reason: Option<i32>, // balance change reason of value transfers
+    pub value_usd: String,   // empty when the token has no price
+}
+
+// Treasury account read from the accounts_metadata table, including the discovered accounts
+#[derive(Debug, Clone)]
+pub struct Account {
+    pub address: String,
+    pub name: String,
+    pub group: String,
+}
+
+// Read a file of length-delimited frenscan.types.v1.Transfers messages, keeping the transfers of a DAO
+pub fn read_transfers_file(file_name: &str, dao_id: &str) -> Vec<Transfer> {
+    let data = std::fs::read(file_name)
+        .unwrap_or_else(|e| panic!("Could not open transfers file {}: {}", file_name, e));
+    let mut buf = &data[..];
+    let mut transfers: Vec<Transfer> = Vec::new();
+
+    while !buf.is_empty() {
+        let block = Transfers::decode_length_delimited(&mut buf)
+            .unwrap_or_else(|e| panic!("Could not read transfers file {}: {}", file_name, e));
+
+        transfers.extend(
+            block
+                .token_transfers
+                .iter()
+                .filter(|t| t.dao_id == dao_id)
+                .map(|t| Transfer {
+                    hash: t.tx_hash.to_string(),
+                    block_number: block.block_number,
+                    timestamp: block.block_timestamp,
+                    ordinal: t.ordinal,
+                    from: t.from.to_string(),
+                    to: t.to.to_string(),
+                    token_address: t.token_address.to_string(),
+                    token_id: t.token_id.to_string(),
+                    value: t.value.to_string(),
+                    reason: None,
+                    value_usd: t.value_usd.to_string(),
+                }),
+        );
+        transfers.extend(
+            block
+                .value_transfers
+                .iter()
+                .filter(|t| t.dao_id == dao_id)
+                .map(|t| Transfer {
+                    hash: t.hash.to_string(),
+                    block_number: block.block_number,
+                    timestamp: block.block_timestamp,
+                    ordinal: t.ordinal,
+                    from: t.from.to_string(),
+                    to: t.to.to_string(),
+                    token_address: "ETH".to_string(),
+                    token_id: "".to_string(),
+                    value: t.value.to_string(),
+                    reason: Some(t.reason),
+                    value_usd: t.value_usd.to_string(),
+                }),
+        );
+    }
+
+    transfers.sort_by_key(|t| (t.block_number, t.ordinal));
+    transfers
+}
+
+// Read the token and value transfers of a DAO from the database written by db_out
+pub fn read_transfers_postgres(
+    client: &mut Client,
+    schema_name: &str,
+    table_prefix: &str,
+    dao_id: &str,
+) -> Vec<Transfer> {
+    let table = |name: &str| format!("{}.{}{}", schema_name, table_prefix, name);
+    let query = format!(
+        "select tx_hash, block_number::bigint, \"timestamp\"::bigint, ordinal::bigint, from_address, to_address,
+             token_address, token_id, value::text, null::integer as reason, coalesce(value_usd::text, '')
+         from {} where dao_id = $1
+         union all
+         select hash, block_number::bigint, \"timestamp\"::bigint, ordinal::bigint, from_address, to_address,
+             'ETH', '', value::text, reason, coalesce(value_usd::text, '')
+         from {} where dao_id = $1
+         order by 2, 4",
+        table("token_transfers"),
+        table("value_transfers")
+    );
+
+    client
+        .query(query.as_str(), &[&dao_id])
+        .unwrap_or_else(|e| panic!("Could not read transfers: {}", e))
+        .iter()
+        .map(|row| Transfer {
+            hash: row.get(0),
+            block_number: row.get::<_, i64>(1) as u64,
+            timestamp: row.get(2),
+            ordinal: row.get::<_, i64>(3) as u64,
+            from: row.get(4),
+            to: row.get(5),
+            token_address: row.get(6),
+            token_id: row.get(7),
+            value: row.get(8),
+            reason: row.get(9),
+            value_usd: row.get(10),
+        })
+        .collect()
+}
+
+// Read a file of length-delimited frenscan.types.v1.DiscoveredAccounts messages, keeping the accounts of a DAO
+pub fn read_discovered_accounts_file(file_name: &str, dao_id: &str) -> Vec<Account> {
+    let data = std::fs::read(file_name).unwrap_or_else(|e| {
+        panic!(
+            "Could not open discovered accounts file {}: {}",
+            file_name, e
+        )
+    });
+    let mut buf = &data[..];
+    let mut accounts: Vec<Account> = Vec::new();
+
+    while !buf.is_empty() {
+        let block = DiscoveredAccounts::decode_length_delimited(&mut buf).unwrap_or_else(|e| {
+            panic!(
+                "Could not read discovered accounts file {}: {}",
+                file_name, e
+            )
+        });
+
+        // Named as in the accounts_metadata table
+        accounts.extend(
+            block
+                .accounts
+                .iter()
+                .filter(|a| a.dao_id == dao_id)
+                .map(|a| Account {
+                    address: a.address.to_string(),
+                    name: if a.factory.is_empty() {
+                        format!("Created by {}", a.creator)
+                    } else {
+                        a.factory.to_string()
+                    },
+                    group: "".to_string(),
+                }),
+        );
+    }
+
+    accounts
+}
+
+pub fn read_accounts_postgres(
+    client: &mut Client,
+    schema_name: &str,
+    table_prefix: &str,
+    dao_id: &str,
+) -> Vec<Account> {
+    let query = format!(
+        "select address, name, \"group\" from {}.{}accounts_metadata where dao_id = $1",
+        schema_name, table_prefix
+    );
+
+    client
+        .query(query.as_str(), &[&dao_id])
+        .unwrap_or_else(|e| panic!("Could not read accounts: {}", e))
+        .iter()
+        .map(|row| Account {
+            address: row.get(0),
+            name: row.get(1),
+            group: row.get(2),
+        })
+        .collect()
+}
+
+// USD price of a token from the token_prices table
+#[derive(Debug, Clone)]
+pub struct TokenPrice {
+    pub token_address: String,
+    pub timestamp: i64,
+    pub price: String,
+}
+
+pub fn read_token_prices_postgres(
+    client: &mut Client,
+    schema_name: &str,
+    table_prefix: &str,
+) -> Vec<TokenPrice> {
+    let query = format!(
+        "select token_address, \"timestamp\"::bigint, price::text from {}.{}token_prices order by 2",
+        schema_name, table_prefix
+    );
+
+    client
+        .query(query.as_str(), &[])
+        .unwrap_or_else(|e| panic!("Could not read token prices: {}", e))
+        .iter()
+        .map(|row| TokenPrice {
+            token_address: row.get(0),
+            timestamp: row.get(1),
+            price: row.get(2),
+        })
+        .collect()
+}
+
+pub fn connect_postgres(dsn: &str) -> Client {
+    Client::connect(dsn, NoTls).unwrap_or_else(|e| panic!("Could not connect to {}: {}", dsn, e))
+}
+
+pub fn normalize_address(addr: &str) -> String {
+    addr.trim_start_matches("0x").to_lowercase()
+}